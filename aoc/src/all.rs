@@ -0,0 +1,248 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::Run;
+use serde_json::{json, Value};
+
+use crate::check;
+use crate::days::Day;
+use crate::report;
+
+// The pool's threads, whose panics end up in the table instead of stderr
+const WORKER: &str = "aoc-worker";
+
+#[derive(Debug)]
+pub enum Outcome {
+    // With the options it was solved with
+    Solved(Run, Value),
+    Failed(String),
+    Panicked(String),
+}
+
+#[derive(Debug)]
+pub struct DayRun {
+    pub day: u8,
+    pub outcome: Outcome,
+    // Of the whole task, reading the input included
+    pub elapsed: Duration,
+}
+
+impl DayRun {
+    pub fn is_ok(&self) -> bool {
+        matches!(self.outcome, Outcome::Solved(..))
+    }
+
+    fn status(&self) -> &'static str {
+        match self.outcome {
+            Outcome::Solved(..) => "ok",
+            Outcome::Failed(_) => "error",
+            Outcome::Panicked(_) => "panic",
+        }
+    }
+}
+
+/// As many jobs as the machine runs threads at once.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |jobs| jobs.get())
+}
+
+fn quiet_workers() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().name() != Some(WORKER) {
+                default(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => String::from(*message),
+            Err(_) => String::from("panicked"),
+        },
+    }
+}
+
+/// Runs `task` for every day on a pool of `jobs` threads, and returns how
+/// each went, by day. A day that panics does not keep the others from
+/// running.
+pub fn run_all<T>(days: &[&'static Day], jobs: usize, task: T) -> Vec<DayRun>
+where
+    T: Fn(&Day) -> Result<(Run, Value), String> + Sync,
+{
+    quiet_workers();
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            let sender = sender.clone();
+            let (next, task) = (&next, &task);
+            thread::Builder::new()
+                .name(String::from(WORKER))
+                .spawn_scoped(scope, move || {
+                    while let Some(day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let start = Instant::now();
+                        let outcome = match panic::catch_unwind(AssertUnwindSafe(|| task(day))) {
+                            Ok(Ok((run, options))) => Outcome::Solved(run, options),
+                            Ok(Err(err)) => Outcome::Failed(err),
+                            Err(payload) => Outcome::Panicked(panic_message(payload)),
+                        };
+                        let run = DayRun {
+                            day: day.number,
+                            outcome,
+                            elapsed: start.elapsed(),
+                        };
+                        sender.send(run).expect("Runs are received until the end");
+                    }
+                })
+                .expect("Could not start a worker thread");
+        }
+    });
+    drop(sender);
+
+    let mut runs: Vec<DayRun> = receiver.into_iter().collect();
+    runs.sort_by_key(|run| run.day);
+    runs
+}
+
+fn answer(run: &Run, part: u8) -> String {
+    match run.answers.iter().find(|(other, _)| other.number() == part) {
+        Some((_, Some(answer))) => check::summary(answer),
+        Some((_, None)) => String::from("no answer"),
+        None => String::from("-"),
+    }
+}
+
+pub fn print_table(runs: &[DayRun], elapsed: Duration) {
+    println!(
+        "{:>3}  {:<6}  {:>12}  {:<24}  Part 2",
+        "Day", "Status", "Time", "Part 1"
+    );
+
+    for run in runs {
+        let (part1, part2) = match &run.outcome {
+            Outcome::Solved(solved, _) => (answer(solved, 1), answer(solved, 2)),
+            _ => (String::from("-"), String::from("-")),
+        };
+        println!(
+            "{:>3}  {:<6}  {:>12}  {:<24}  {}",
+            run.day,
+            run.status(),
+            format!("{:.3?}", run.elapsed),
+            part1,
+            part2
+        );
+    }
+
+    for run in runs {
+        match &run.outcome {
+            Outcome::Failed(err) => println!("\nDay {}: {err}", run.day),
+            Outcome::Panicked(message) => println!("\nDay {} panicked: {message}", run.day),
+            Outcome::Solved(..) => (),
+        }
+    }
+
+    let solved = runs.iter().filter(|run| run.is_ok()).count();
+    println!(
+        "\n{} days in {elapsed:.3?}: {solved} solved, {} failed",
+        runs.len(),
+        runs.len() - solved
+    );
+}
+
+/// Every day's run as a JSON document, like the one of a single day, with
+/// its status and what went wrong, if anything.
+pub fn document(runs: &[DayRun], elapsed: Duration) -> Value {
+    let days: Vec<Value> = runs
+        .iter()
+        .map(|run| {
+            let mut document = match &run.outcome {
+                Outcome::Solved(solved, options) => report::document(run.day, solved, options),
+                Outcome::Failed(err) | Outcome::Panicked(err) => {
+                    json!({ "day": run.day, "error": err })
+                }
+            };
+            document["status"] = json!(run.status());
+            document["elapsed_ns"] = json!(run.elapsed.as_nanos() as u64);
+            document
+        })
+        .collect();
+
+    json!({
+        "days": days,
+        "elapsed_ns": elapsed.as_nanos() as u64,
+        "failed": runs.iter().filter(|run| !run.is_ok()).count(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use common::Part;
+
+    fn solved(day: &Day) -> Run {
+        Run {
+            answers: vec![(Part::One, Some(day.number.to_string())), (Part::Two, None)],
+            parse: Duration::ZERO,
+            parts: vec![Duration::ZERO; 2],
+            details: None,
+        }
+    }
+
+    #[test]
+    fn panics_are_isolated() {
+        let all: Vec<&'static Day> = days::DAYS.iter().collect();
+        let runs = run_all(&all, 3, |day| match day.number {
+            2 => panic!("day {} exploded", day.number),
+            3 => Err(String::from("could not read the input")),
+            _ => Ok((solved(day), json!({}))),
+        });
+
+        assert_eq!(runs.len(), all.len());
+        assert!(runs.windows(2).all(|pair| pair[0].day < pair[1].day));
+        assert!(
+            matches!(&runs[1].outcome, Outcome::Panicked(message) if message == "day 2 exploded")
+        );
+        assert!(matches!(&runs[2].outcome, Outcome::Failed(_)));
+        assert_eq!(runs.iter().filter(|run| run.is_ok()).count(), all.len() - 2);
+
+        let document = document(&runs, Duration::from_millis(1));
+        assert_eq!(document["failed"], 2);
+        assert_eq!(document["days"][0]["answers"]["part1"], "1");
+        assert_eq!(document["days"][0]["status"], "ok");
+        assert_eq!(document["days"][1]["status"], "panic");
+        assert_eq!(document["days"][2]["error"], "could not read the input");
+    }
+
+    #[test]
+    fn more_jobs_than_days() {
+        let some: Vec<&'static Day> = days::DAYS.iter().take(2).collect();
+        let runs = run_all(&some, 16, |day| Ok((solved(day), json!({}))));
+        assert_eq!(runs.len(), 2);
+        assert!(run_all(&[], 4, |day| Ok((solved(day), json!({})))).is_empty());
+    }
+
+    #[test]
+    fn answers() {
+        let run = Run {
+            answers: vec![(Part::Two, Some(String::from("#.\n.#\n")))],
+            parse: Duration::ZERO,
+            parts: vec![Duration::ZERO],
+            details: None,
+        };
+        assert_eq!(answer(&run, 1), "-");
+        assert_eq!(answer(&run, 2), "#. (+1 lines)");
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use common::Part;
+use serde::Deserialize;
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table};
+
+// First line of the answers files made by submitting an answer
+const HEADER: &str = "# Known good answers, checked with `aoc --check`\n\n";
+
+/// Why the website did not accept an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Rejection {
+    #[serde(rename = "too high")]
+    TooHigh,
+    #[serde(rename = "too low")]
+    TooLow,
+    #[serde(rename = "wrong")]
+    Wrong,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rejection = match self {
+            Rejection::TooHigh => "too high",
+            Rejection::TooLow => "too low",
+            Rejection::Wrong => "wrong",
+        };
+        f.write_str(rejection)
+    }
+}
+
+/// An answer submitted before, and not accepted.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct Rejected {
+    part: u8,
+    answer: String,
+    verdict: Rejection,
+}
+
+impl Rejected {
+    // Too high or too low answers also rule out everything beyond them
+    fn rules_out(&self, answer: &str) -> bool {
+        if self.answer == answer {
+            return true;
+        }
+        let (Ok(answer), Ok(rejected)) = (answer.parse::<i128>(), self.answer.parse::<i128>())
+        else {
+            return false;
+        };
+        match self.verdict {
+            Rejection::TooHigh => answer >= rejected,
+            Rejection::TooLow => answer <= rejected,
+            Rejection::Wrong => false,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+pub struct Expected {
+    part1: Option<String>,
+    part2: Option<String>,
+    #[serde(default)]
+    rejected: Vec<Rejected>,
+}
+
+/// What is already known about an answer, before submitting it.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Known<'a> {
+    Accepted,
+    // Another answer, or the same one drawn instead of written, is the right one
+    OtherAccepted(&'a str),
+    // Ruled out by an answer rejected before, which may not be this one
+    Rejected(Rejection, &'a str),
+}
+
+impl Expected {
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+/// Known good answers of a day, by input.
+///
+/// Inputs are named after their file in the day's `data/` folder,
+/// without the extension, like `test-input` or `input`.
+#[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Registry {
+    inputs: BTreeMap<String, Expected>,
+}
+
+impl Registry {
+    pub fn from_toml(string: &str) -> Result<Registry, String> {
+        toml::from_str(string).map_err(|err| err.to_string())
+    }
+
+    // A day without an answers file simply has no known answers yet
+    pub fn load(path: &Path) -> Result<Registry, String> {
+        match fs::read_to_string(path) {
+            Ok(string) => Registry::from_toml(&string)
+                .map_err(|err| format!("invalid answers file {}: {err}", path.display())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Registry::default()),
+            Err(err) => Err(format!("could not read {}: {err}", path.display())),
+        }
+    }
+
+    pub fn get(&self, input: &str) -> Option<&Expected> {
+        self.inputs.get(input)
+    }
+
+    pub fn inputs(&self) -> impl Iterator<Item = &str> {
+        self.inputs.keys().map(String::as_str)
+    }
+
+    pub fn known(&self, input: &str, part: Part, answer: &str) -> Option<Known<'_>> {
+        let expected = self.get(input)?;
+        if let Some(accepted) = expected.get(part) {
+            return Some(if accepted.trim_end() == answer.trim_end() {
+                Known::Accepted
+            } else {
+                Known::OtherAccepted(accepted)
+            });
+        }
+
+        expected
+            .rejected
+            .iter()
+            .filter(|rejected| rejected.part == part.number())
+            .find(|rejected| rejected.rules_out(answer))
+            .map(|rejected| Known::Rejected(rejected.verdict, &rejected.answer))
+    }
+
+    /// Adds the website's verdict on an answer to the answers file,
+    /// keeping the rest of it as it is.
+    pub fn record(
+        path: &Path,
+        input: &str,
+        part: Part,
+        answer: &str,
+        rejection: Option<Rejection>,
+    ) -> Result<(), String> {
+        let (header, string) = match fs::read_to_string(path) {
+            Ok(string) => ("", string),
+            Err(err) if err.kind() == ErrorKind::NotFound => (HEADER, String::new()),
+            Err(err) => return Err(format!("could not read {}: {err}", path.display())),
+        };
+        let invalid =
+            |err: &dyn fmt::Display| format!("invalid answers file {}: {err}", path.display());
+
+        let mut document: DocumentMut = string.parse().map_err(|err| invalid(&err))?;
+        let table = document
+            .entry(input)
+            .or_insert(Item::Table(Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| invalid(&format!("`{input}` is not a table")))?;
+
+        match rejection {
+            None => table[&format!("part{part}")] = toml_edit::value(answer),
+            Some(rejection) => {
+                let mut rejected = Table::new();
+                rejected["part"] = toml_edit::value(i64::from(part.number()));
+                rejected["answer"] = toml_edit::value(answer);
+                rejected["verdict"] = toml_edit::value(rejection.to_string());
+                table
+                    .entry("rejected")
+                    .or_insert(Item::ArrayOfTables(ArrayOfTables::new()))
+                    .as_array_of_tables_mut()
+                    .ok_or_else(|| invalid(&format!("`{input}.rejected` is not a list of tables")))?
+                    .push(rejected);
+            }
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|err| format!("could not create {}: {err}", dir.display()))?;
+        }
+        fs::write(path, format!("{header}{document}"))
+            .map_err(|err| format!("could not write {}: {err}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_toml() {
+        let registry = Registry::from_toml(
+            r#"
+[test-input]
+part1 = "CMZ"
+
+[input]
+part1 = "12460"
+part2 = """
+##..
+.##.
+"""
+"#,
+        )
+        .unwrap();
+
+        let test = registry.get("test-input").unwrap();
+        assert_eq!(test.get(Part::One), Some("CMZ"));
+        assert_eq!(test.get(Part::Two), None);
+
+        let input = registry.get("input").unwrap();
+        assert_eq!(input.get(Part::One), Some("12460"));
+        assert_eq!(input.get(Part::Two), Some("##..\n.##.\n"));
+
+        assert!(registry.get("other").is_none());
+        assert_eq!(
+            registry.inputs().collect::<Vec<_>>(),
+            ["input", "test-input"]
+        );
+    }
+
+    #[test]
+    fn known_answers() {
+        let registry = Registry::from_toml(
+            r#"
+[input]
+part1 = "71471"
+
+[[input.rejected]]
+part = 2
+answer = "300"
+verdict = "too high"
+
+[[input.rejected]]
+part = 2
+answer = "100"
+verdict = "too low"
+
+[[input.rejected]]
+part = 2
+answer = "150"
+verdict = "wrong"
+"#,
+        )
+        .unwrap();
+
+        let known = |part, answer| registry.known("input", part, answer);
+        assert_eq!(known(Part::One, "71471"), Some(Known::Accepted));
+        assert_eq!(known(Part::One, "1"), Some(Known::OtherAccepted("71471")));
+        assert_eq!(
+            known(Part::Two, "301"),
+            Some(Known::Rejected(Rejection::TooHigh, "300"))
+        );
+        assert_eq!(
+            known(Part::Two, "100"),
+            Some(Known::Rejected(Rejection::TooLow, "100"))
+        );
+        assert_eq!(
+            known(Part::Two, "150"),
+            Some(Known::Rejected(Rejection::Wrong, "150"))
+        );
+        assert_eq!(known(Part::Two, "200"), None);
+        assert_eq!(known(Part::Two, "ABC"), None);
+        assert_eq!(registry.known("test-input", Part::One, "1"), None);
+    }
+
+    #[test]
+    fn record() {
+        let path = crate::testing::scratch_dir("record").join("answers.toml");
+
+        Registry::record(&path, "input", Part::One, "12", Some(Rejection::TooLow)).unwrap();
+        Registry::record(&path, "input", Part::One, "99", Some(Rejection::Wrong)).unwrap();
+        Registry::record(&path, "input", Part::One, "42", None).unwrap();
+        Registry::record(&path, "input", Part::Two, "##\n..\n", None).unwrap();
+
+        let string = fs::read_to_string(&path).unwrap();
+        assert!(string.starts_with(HEADER));
+
+        let registry = Registry::load(&path).unwrap();
+        let expected = registry.get("input").unwrap();
+        assert_eq!(expected.get(Part::One), Some("42"));
+        assert_eq!(expected.get(Part::Two), Some("##\n..\n"));
+        assert_eq!(
+            expected.rejected,
+            [
+                Rejected {
+                    part: 1,
+                    answer: String::from("12"),
+                    verdict: Rejection::TooLow
+                },
+                Rejected {
+                    part: 1,
+                    answer: String::from("99"),
+                    verdict: Rejection::Wrong
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_toml() {
+        assert!(Registry::from_toml("[input]\npart1 = 12").is_err());
+    }
+
+    #[test]
+    fn every_day_has_answers() {
+        for day in crate::testing::solved_days() {
+            let registry = Registry::load(&day.answers_file()).unwrap();
+            for input in ["test-input", "input"] {
+                let expected = registry.get(input).unwrap();
+                assert!(expected.get(Part::One).is_some(), "day {}", day.number);
+                assert!(expected.get(Part::Two).is_some(), "day {}", day.number);
+            }
+        }
+    }
+}
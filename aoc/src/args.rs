@@ -0,0 +1,1194 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use common::trace::Filter;
+use common::{visual, Part};
+
+pub const USAGE: &str = "\
+Usage: aoc --day <N> [--part <1|2>] [--input <PATH|->] [--format <text|json>]
+                 [--set <NAME=VALUE>...] [--trace <PATH>] [--trace-filter <FILTER>]
+       aoc --all [--part <1|2>] [--format <text|json>] [--jobs <N>]
+                 [--trace <PATH>] [--trace-filter <FILTER>]
+       aoc --check [--day <N>] [--part <1|2>]
+       aoc --bench [--day <N>] [--input <PATH|->] [--iterations <N>]
+                   [--baseline <PATH>] [--save-baseline <PATH>] [--threshold <PERCENT>]
+                   [--set <NAME=VALUE>...]
+       aoc --visualize <PATH> --day <N> [--part <1|2>] [--input <PATH|->]
+                 [--visual-format <FORMAT>] [--max-frames <N>] [--set <NAME=VALUE>...]
+       aoc --generate --day <N> [--size <N>] [--seed <N>]
+       aoc --download --day <N> [--force]
+       aoc --submit --day <N> --part <1|2> [--answer <ANSWER>]
+       aoc new --day <N>
+       aoc watch --day <N>
+
+Options:
+  --day <N>             Day to solve
+  --part <1|2>          Only solve this part, instead of both
+  --input <PATH|->      Read the input from PATH, or from stdin with `-`
+                        (defaults to dayNN/data/input.txt)
+  --format <text|json>  Print the answers as text, or as a JSON document
+                        with timings and the day's details (defaults to text)
+  --set <NAME=VALUE>    Change one of the puzzle's options, like a threshold
+                        or a size, over its value in aoc.toml if any; VALUE
+                        is written like in TOML, a bare word being a string
+  --trace <PATH>        Write what the solvers do, like the moves of day 5 or
+                        the cycles of day 10, to PATH as JSON lines
+  --trace-filter <FILTER>
+                        Which events are traced, as comma separated levels
+                        (error, warn, info, debug, trace or off) for every
+                        module or for one, like `debug,day09=trace`
+                        (defaults to trace, keeping them all)
+  --all                 Solve every day at once, each on its own input, and
+                        print a table of their answers, timings and errors
+  --jobs <N>            How many days are solved at the same time (defaults
+                        to the number of threads the machine runs at once)
+  --check               Compare the answers of every day, or only of --day,
+                        with the known ones in dayNN/data/answers.toml
+  --bench               Time parsing and each part of every day, or only of --day
+  --iterations <N>      How many times each day is benchmarked (defaults to 10)
+  --baseline <PATH>     Compare the benchmark with a baseline saved before
+  --save-baseline <PATH>
+                        Save the benchmark as a baseline, in JSON
+  --threshold <PERCENT> How much slower than the baseline a step can get
+                        before it is a regression (defaults to 10)
+  --visualize <PATH>    Draw how --day solves --part (defaults to 1) frame by
+                        frame, for the days that can, into PATH
+  --visual-format <FORMAT>
+                        ascii, svg (the last frame), animated-svg or apng
+                        (defaults to ascii for .txt, animated-svg for .svg
+                        and apng for .png or .apng)
+  --max-frames <N>      How many frames are drawn at most, evenly picked
+                        from all of them (defaults to 500)
+  --generate            Print a random valid input for --day
+  --size <N>            How big the generated input is, in the day's own unit
+                        (defaults to about the size of a real input)
+  --seed <N>            Seed of the generated input, the same seed giving
+                        the same input (defaults to a random one)
+  --download            Download the input of --day into dayNN/data/input.txt,
+                        with the session token of aoc.toml (or of $AOC_CONFIG)
+  --force               Download the input again, even if it is already there
+  --submit              Submit the answer of --day and --part to the website,
+                        unless dayNN/data/answers.toml already rules it out,
+                        and record the verdict there
+  --answer <ANSWER>     Answer to submit, instead of the one solved from
+                        dayNN/data/input.txt
+  new, --new            Create the dayNN crate of --day from a template,
+                        and register it with the workspace and the runner
+  watch, --watch        Run the tests and every input of --day again each time
+                        a file in its src/ or data/ changes, showing how the
+                        answers changed
+  --help                Print this message";
+
+pub const DEFAULT_ITERATIONS: usize = 10;
+pub const DEFAULT_THRESHOLD: u32 = 10;
+pub const DEFAULT_MAX_FRAMES: usize = 500;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Input {
+    Default,
+    Stdin,
+    Path(PathBuf),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Options of the puzzle set on the command line, by name, with their
+/// values as written.
+pub type Overrides = Vec<(String, String)>;
+
+/// Where to write the solvers' events, and which ones.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Trace {
+    pub path: PathBuf,
+    pub filter: Filter,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Args {
+    pub day: u8,
+    pub part: Option<Part>,
+    pub input: Input,
+    pub format: Format,
+    pub options: Overrides,
+    pub trace: Option<Trace>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AllArgs {
+    pub part: Option<Part>,
+    pub format: Format,
+    // The machine's parallelism when not given
+    pub jobs: Option<usize>,
+    pub trace: Option<Trace>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BenchArgs {
+    pub day: Option<u8>,
+    pub input: Input,
+    pub iterations: usize,
+    pub baseline: Option<PathBuf>,
+    pub save_baseline: Option<PathBuf>,
+    // In percent of the baseline's median
+    pub threshold: u32,
+    pub options: Overrides,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct VisualizeArgs {
+    pub day: u8,
+    pub part: Part,
+    pub input: Input,
+    pub options: Overrides,
+    pub path: PathBuf,
+    pub format: visual::Format,
+    pub max_frames: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct GenerateArgs {
+    pub day: u8,
+    pub size: Option<usize>,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DownloadArgs {
+    pub day: u8,
+    // Even when the input was downloaded before
+    pub force: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SubmitArgs {
+    pub day: u8,
+    pub part: Part,
+    // Solved from the day's input when not given
+    pub answer: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Help,
+    Solve(Args),
+    All(AllArgs),
+    Check { day: Option<u8>, part: Option<Part> },
+    Bench(BenchArgs),
+    Visualize(VisualizeArgs),
+    Generate(GenerateArgs),
+    Download(DownloadArgs),
+    Submit(SubmitArgs),
+    New { day: u8 },
+    Watch { day: u8 },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ArgsError {
+    Unknown(String),
+    MissingValue(&'static str),
+    Invalid(&'static str, String),
+    Missing(&'static str),
+    MissingFor(&'static str, &'static str),
+    Conflict(&'static str, &'static str),
+}
+
+impl ArgsError {
+    fn needed_by(self, other: &'static str) -> ArgsError {
+        match self {
+            ArgsError::Missing(option) => ArgsError::MissingFor(option, other),
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgsError::Unknown(arg) => write!(f, "unknown argument `{arg}`"),
+            ArgsError::MissingValue(option) => write!(f, "`{option}` needs a value"),
+            ArgsError::Invalid(option, value) => {
+                write!(f, "invalid value `{value}` for `{option}`")
+            }
+            ArgsError::Missing(option) => write!(f, "`{option}` is required"),
+            ArgsError::MissingFor(option, other) => {
+                write!(f, "`{option}` is required by `{other}`")
+            }
+            ArgsError::Conflict(option, other) => {
+                write!(f, "`{option}` cannot be used with `{other}`")
+            }
+        }
+    }
+}
+
+impl Command {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, ArgsError> {
+        let mut args = args.into_iter().peekable();
+
+        let mut day = None;
+        let mut part = None;
+        let mut input = Input::Default;
+        let mut format = None;
+        let mut check = false;
+        let mut bench = false;
+        let mut iterations = None;
+        let mut baseline = None;
+        let mut save_baseline = None;
+        let mut threshold = None;
+        let mut generate = false;
+        let mut size = None;
+        let mut seed = None;
+        let mut download = false;
+        let mut force = false;
+        let mut submit = false;
+        let mut answer = None;
+        // As they were asked for, a subcommand or a flag
+        let mut new = None;
+        let mut watch = None;
+        let mut options = Overrides::new();
+        let mut all = false;
+        let mut jobs = None;
+        let mut trace_path: Option<PathBuf> = None;
+        let mut trace_filter = None;
+        let mut visualize: Option<PathBuf> = None;
+        let mut visual_format = None;
+        let mut max_frames = None;
+
+        match args.peek().map(String::as_str) {
+            Some("new") => new = Some("new"),
+            Some("watch") => watch = Some("watch"),
+            _ => {}
+        }
+        if new.is_some() || watch.is_some() {
+            args.next();
+        }
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--help" | "-h" => return Ok(Command::Help),
+                "--day" => day = Some(value(&mut args, "--day")?),
+                "--part" => {
+                    let value = args.next().ok_or(ArgsError::MissingValue("--part"))?;
+                    part = match value.as_str() {
+                        "1" => Some(Part::One),
+                        "2" => Some(Part::Two),
+                        _ => return Err(ArgsError::Invalid("--part", value)),
+                    };
+                }
+                "--input" => {
+                    let value = args.next().ok_or(ArgsError::MissingValue("--input"))?;
+                    input = match value.as_str() {
+                        "-" => Input::Stdin,
+                        _ => Input::Path(PathBuf::from(value)),
+                    };
+                }
+                "--format" => {
+                    let value = args.next().ok_or(ArgsError::MissingValue("--format"))?;
+                    format = match value.as_str() {
+                        "text" => Some(Format::Text),
+                        "json" => Some(Format::Json),
+                        _ => return Err(ArgsError::Invalid("--format", value)),
+                    };
+                }
+                "--check" => check = true,
+                "--bench" => bench = true,
+                "--iterations" => iterations = Some(value(&mut args, "--iterations")?),
+                "--baseline" => baseline = Some(value(&mut args, "--baseline")?),
+                "--save-baseline" => save_baseline = Some(value(&mut args, "--save-baseline")?),
+                "--threshold" => threshold = Some(value(&mut args, "--threshold")?),
+                "--generate" => generate = true,
+                "--size" => size = Some(value(&mut args, "--size")?),
+                "--seed" => seed = Some(value(&mut args, "--seed")?),
+                "--download" => download = true,
+                "--force" => force = true,
+                "--submit" => submit = true,
+                "--answer" => answer = Some(value(&mut args, "--answer")?),
+                "--new" => new = new.or(Some("--new")),
+                "--watch" => watch = watch.or(Some("--watch")),
+                "--all" => all = true,
+                "--jobs" => jobs = Some(value(&mut args, "--jobs")?),
+                "--visualize" => visualize = Some(value(&mut args, "--visualize")?),
+                "--visual-format" => visual_format = Some(value(&mut args, "--visual-format")?),
+                "--max-frames" => max_frames = Some(value(&mut args, "--max-frames")?),
+                "--trace" => trace_path = Some(value(&mut args, "--trace")?),
+                "--trace-filter" => {
+                    let value = args
+                        .next()
+                        .ok_or(ArgsError::MissingValue("--trace-filter"))?;
+                    match value.parse() {
+                        Ok(filter) => trace_filter = Some(filter),
+                        Err(_) => return Err(ArgsError::Invalid("--trace-filter", value)),
+                    }
+                }
+                "--set" => {
+                    let value = args.next().ok_or(ArgsError::MissingValue("--set"))?;
+                    match value.split_once('=') {
+                        Some((name, option)) if !name.trim().is_empty() => {
+                            options.push((name.trim().to_string(), option.trim().to_string()))
+                        }
+                        _ => return Err(ArgsError::Invalid("--set", value)),
+                    }
+                }
+                _ => return Err(ArgsError::Unknown(arg)),
+            }
+        }
+
+        let (new, new_arg) = (new.is_some(), new.unwrap_or("--new"));
+        let (watch, watch_arg) = (watch.is_some(), watch.unwrap_or("--watch"));
+
+        if jobs.is_some() && !all {
+            return Err(ArgsError::Missing("--all").needed_by("--jobs"));
+        }
+
+        if trace_filter.is_some() && trace_path.is_none() {
+            return Err(ArgsError::Missing("--trace").needed_by("--trace-filter"));
+        }
+        let trace = trace_path.map(|path| Trace {
+            path,
+            filter: trace_filter.unwrap_or_else(|| "trace".parse().expect("A valid filter")),
+        });
+
+        // Only solving traces the solvers
+        if trace.is_some() {
+            for (other, is_set) in [
+                ("--check", check),
+                ("--bench", bench),
+                ("--generate", generate),
+                ("--download", download),
+                ("--submit", submit),
+                (new_arg, new),
+                (watch_arg, watch),
+                ("--visualize", visualize.is_some()),
+            ] {
+                if is_set {
+                    return Err(ArgsError::Conflict("--trace", other));
+                }
+            }
+        }
+
+        if all {
+            for (other, is_set) in [
+                ("--day", day.is_some()),
+                ("--input", input != Input::Default),
+                ("--set", !options.is_empty()),
+                ("--check", check),
+                ("--bench", bench),
+                ("--generate", generate),
+                ("--download", download),
+                ("--submit", submit),
+                (new_arg, new),
+                (watch_arg, watch),
+                ("--visualize", visualize.is_some()),
+            ] {
+                if is_set {
+                    return Err(ArgsError::Conflict("--all", other));
+                }
+            }
+            if jobs == Some(0) {
+                return Err(ArgsError::Invalid("--jobs", String::from("0")));
+            }
+            return Ok(Command::All(AllArgs {
+                part,
+                format: format.unwrap_or_default(),
+                jobs,
+                trace,
+            }));
+        }
+
+        // Only solving and benchmarking can change the puzzle
+        if !options.is_empty() {
+            for (other, is_set) in [
+                ("--check", check),
+                ("--generate", generate),
+                ("--download", download),
+                ("--submit", submit),
+                (new_arg, new),
+                (watch_arg, watch),
+            ] {
+                if is_set {
+                    return Err(ArgsError::Conflict("--set", other));
+                }
+            }
+        }
+
+        if !bench {
+            for (option, is_set) in [
+                ("--iterations", iterations.is_some()),
+                ("--baseline", baseline.is_some()),
+                ("--save-baseline", save_baseline.is_some()),
+                ("--threshold", threshold.is_some()),
+            ] {
+                if is_set {
+                    return Err(ArgsError::Missing("--bench").needed_by(option));
+                }
+            }
+        }
+
+        if format.is_some() {
+            for (other, is_set) in [
+                ("--check", check),
+                ("--bench", bench),
+                ("--generate", generate),
+            ] {
+                if is_set {
+                    return Err(ArgsError::Conflict("--format", other));
+                }
+            }
+        }
+
+        if force && !download {
+            return Err(ArgsError::Missing("--download").needed_by("--force"));
+        }
+
+        if answer.is_some() && !submit {
+            return Err(ArgsError::Missing("--submit").needed_by("--answer"));
+        }
+
+        if !generate {
+            for (option, is_set) in [("--size", size.is_some()), ("--seed", seed.is_some())] {
+                if is_set {
+                    return Err(ArgsError::Missing("--generate").needed_by(option));
+                }
+            }
+        }
+
+        if visualize.is_none() {
+            for (option, is_set) in [
+                ("--visual-format", visual_format.is_some()),
+                ("--max-frames", max_frames.is_some()),
+            ] {
+                if is_set {
+                    return Err(ArgsError::Missing("--visualize").needed_by(option));
+                }
+            }
+        }
+
+        if let Some(path) = visualize {
+            for (other, is_set) in [
+                ("--check", check),
+                ("--bench", bench),
+                ("--generate", generate),
+                ("--download", download),
+                ("--submit", submit),
+                (new_arg, new),
+                (watch_arg, watch),
+                ("--format", format.is_some()),
+            ] {
+                if is_set {
+                    return Err(ArgsError::Conflict("--visualize", other));
+                }
+            }
+            let day = day.ok_or(ArgsError::Missing("--day").needed_by("--visualize"))?;
+            // Unless given, the format goes with the file's extension
+            let format = match visual_format {
+                Some(format) => format,
+                None => path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .and_then(visual::Format::from_extension)
+                    .ok_or(ArgsError::Missing("--visual-format").needed_by("--visualize"))?,
+            };
+            let max_frames = max_frames.unwrap_or(DEFAULT_MAX_FRAMES);
+            if max_frames == 0 {
+                return Err(ArgsError::Invalid("--max-frames", String::from("0")));
+            }
+            return Ok(Command::Visualize(VisualizeArgs {
+                day,
+                part: part.unwrap_or(Part::One),
+                input,
+                options,
+                path,
+                format,
+                max_frames,
+            }));
+        }
+
+        if watch {
+            for (other, is_set) in [
+                ("--check", check),
+                ("--bench", bench),
+                ("--generate", generate),
+                ("--download", download),
+                ("--submit", submit),
+                (new_arg, new),
+                ("--part", part.is_some()),
+                ("--input", input != Input::Default),
+                ("--format", format.is_some()),
+            ] {
+                if is_set {
+                    return Err(ArgsError::Conflict(watch_arg, other));
+                }
+            }
+            let day = day.ok_or(ArgsError::Missing("--day").needed_by(watch_arg))?;
+            return Ok(Command::Watch { day });
+        }
+
+        if new {
+            for (other, is_set) in [
+                ("--check", check),
+                ("--bench", bench),
+                ("--generate", generate),
+                ("--download", download),
+                ("--submit", submit),
+                ("--part", part.is_some()),
+                ("--input", input != Input::Default),
+                ("--format", format.is_some()),
+            ] {
+                if is_set {
+                    return Err(ArgsError::Conflict(new_arg, other));
+                }
+            }
+            let day: u8 = day.ok_or(ArgsError::Missing("--day").needed_by(new_arg))?;
+            if !(1..=25).contains(&day) {
+                return Err(ArgsError::Invalid("--day", day.to_string()));
+            }
+            return Ok(Command::New { day });
+        }
+
+        if submit {
+            for (other, is_set) in [
+                ("--check", check),
+                ("--bench", bench),
+                ("--generate", generate),
+                ("--download", download),
+                ("--input", input != Input::Default),
+                ("--format", format.is_some()),
+            ] {
+                if is_set {
+                    return Err(ArgsError::Conflict("--submit", other));
+                }
+            }
+            let day: u8 = day.ok_or(ArgsError::Missing("--day").needed_by("--submit"))?;
+            if !(1..=25).contains(&day) {
+                return Err(ArgsError::Invalid("--day", day.to_string()));
+            }
+            let part = part.ok_or(ArgsError::Missing("--part").needed_by("--submit"))?;
+            return Ok(Command::Submit(SubmitArgs { day, part, answer }));
+        }
+
+        if download {
+            for (other, is_set) in [
+                ("--check", check),
+                ("--bench", bench),
+                ("--generate", generate),
+                ("--part", part.is_some()),
+                ("--input", input != Input::Default),
+                ("--format", format.is_some()),
+            ] {
+                if is_set {
+                    return Err(ArgsError::Conflict("--download", other));
+                }
+            }
+            let day: u8 = day.ok_or(ArgsError::Missing("--day").needed_by("--download"))?;
+            if !(1..=25).contains(&day) {
+                return Err(ArgsError::Invalid("--day", day.to_string()));
+            }
+            return Ok(Command::Download(DownloadArgs { day, force }));
+        }
+
+        if generate {
+            for (other, is_set) in [
+                ("--check", check),
+                ("--bench", bench),
+                ("--part", part.is_some()),
+                ("--input", input != Input::Default),
+            ] {
+                if is_set {
+                    return Err(ArgsError::Conflict("--generate", other));
+                }
+            }
+            let day = day.ok_or(ArgsError::Missing("--day").needed_by("--generate"))?;
+            return Ok(Command::Generate(GenerateArgs { day, size, seed }));
+        }
+
+        if check {
+            if bench {
+                return Err(ArgsError::Conflict("--check", "--bench"));
+            }
+            if input != Input::Default {
+                return Err(ArgsError::Conflict("--check", "--input"));
+            }
+            return Ok(Command::Check { day, part });
+        }
+
+        if bench {
+            if part.is_some() {
+                return Err(ArgsError::Conflict("--bench", "--part"));
+            }
+            if input != Input::Default && day.is_none() {
+                return Err(ArgsError::Missing("--day").needed_by("--input"));
+            }
+            // Every day has options of its own
+            if !options.is_empty() && day.is_none() {
+                return Err(ArgsError::Missing("--day").needed_by("--set"));
+            }
+            let iterations = iterations.unwrap_or(DEFAULT_ITERATIONS);
+            if iterations == 0 {
+                return Err(ArgsError::Invalid("--iterations", String::from("0")));
+            }
+            return Ok(Command::Bench(BenchArgs {
+                day,
+                input,
+                iterations,
+                baseline,
+                save_baseline,
+                threshold: threshold.unwrap_or(DEFAULT_THRESHOLD),
+                options,
+            }));
+        }
+
+        let day = day.ok_or(ArgsError::Missing("--day"))?;
+
+        Ok(Command::Solve(Args {
+            day,
+            part,
+            input,
+            format: format.unwrap_or_default(),
+            options,
+            trace,
+        }))
+    }
+}
+
+fn value<T: FromStr>(
+    args: &mut impl Iterator<Item = String>,
+    option: &'static str,
+) -> Result<T, ArgsError> {
+    let value = args.next().ok_or(ArgsError::MissingValue(option))?;
+    value.parse().map_err(|_| ArgsError::Invalid(option, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::trace::Level;
+
+    fn parse(args: &[&str]) -> Result<Command, ArgsError> {
+        Command::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn solve() {
+        assert_eq!(
+            parse(&["--day", "7"]),
+            Ok(Command::Solve(Args {
+                day: 7,
+                part: None,
+                input: Input::Default,
+                format: Format::Text,
+                options: Vec::new(),
+                trace: None
+            }))
+        );
+        assert_eq!(
+            parse(&["--part", "2", "--input", "-", "--day", "10"]),
+            Ok(Command::Solve(Args {
+                day: 10,
+                part: Some(Part::Two),
+                input: Input::Stdin,
+                format: Format::Text,
+                options: Vec::new(),
+                trace: None
+            }))
+        );
+        assert_eq!(
+            parse(&["--day", "1", "--input", "some/file.txt"]),
+            Ok(Command::Solve(Args {
+                day: 1,
+                part: None,
+                input: Input::Path(PathBuf::from("some/file.txt")),
+                format: Format::Text,
+                options: Vec::new(),
+                trace: None
+            }))
+        );
+        assert_eq!(
+            parse(&["--day", "7", "--format", "json"]),
+            Ok(Command::Solve(Args {
+                day: 7,
+                part: None,
+                input: Input::Default,
+                format: Format::Json,
+                options: Vec::new(),
+                trace: None
+            }))
+        );
+        assert_eq!(
+            parse(&["--day", "7", "--format", "yaml"]),
+            Err(ArgsError::Invalid("--format", String::from("yaml")))
+        );
+        assert_eq!(
+            parse(&["--check", "--format", "json"]),
+            Err(ArgsError::Conflict("--format", "--check"))
+        );
+    }
+
+    #[test]
+    fn options() {
+        assert_eq!(
+            parse(&[
+                "--day",
+                "7",
+                "--set",
+                "max_size=1000",
+                "--set",
+                "total_size = 5e7"
+            ]),
+            Ok(Command::Solve(Args {
+                day: 7,
+                part: None,
+                input: Input::Default,
+                format: Format::Text,
+                options: vec![
+                    (String::from("max_size"), String::from("1000")),
+                    (String::from("total_size"), String::from("5e7"))
+                ],
+                trace: None
+            }))
+        );
+        assert!(matches!(
+            parse(&["--bench", "--day", "9", "--set", "knot_count=3"]),
+            Ok(Command::Bench(BenchArgs { day: Some(9), ref options, .. })) if options.len() == 1
+        ));
+        assert_eq!(
+            parse(&["--day", "7", "--set", "max_size"]),
+            Err(ArgsError::Invalid("--set", String::from("max_size")))
+        );
+        assert_eq!(
+            parse(&["--day", "7", "--set", "=1"]),
+            Err(ArgsError::Invalid("--set", String::from("=1")))
+        );
+        assert_eq!(
+            parse(&["--bench", "--set", "knot_count=3"]),
+            Err(ArgsError::MissingFor("--day", "--set"))
+        );
+        assert_eq!(
+            parse(&["--check", "--set", "knot_count=3"]),
+            Err(ArgsError::Conflict("--set", "--check"))
+        );
+    }
+
+    #[test]
+    fn trace() {
+        match parse(&["--day", "9", "--trace", "trace.jsonl"]) {
+            Ok(Command::Solve(Args {
+                trace: Some(trace), ..
+            })) => {
+                assert_eq!(trace.path, PathBuf::from("trace.jsonl"));
+                assert!(trace.filter.enabled(Level::Trace, "day09::rope"));
+            }
+            other => panic!("expected a traced solve, got {other:?}"),
+        }
+        match parse(&[
+            "--all",
+            "--trace",
+            "trace.jsonl",
+            "--trace-filter",
+            "off,day05=debug",
+        ]) {
+            Ok(Command::All(AllArgs {
+                trace: Some(trace), ..
+            })) => {
+                assert!(trace.filter.enabled(Level::Debug, "day05"));
+                assert!(!trace.filter.enabled(Level::Error, "day07"));
+            }
+            other => panic!("expected a traced --all, got {other:?}"),
+        }
+        assert_eq!(
+            parse(&[
+                "--day",
+                "9",
+                "--trace",
+                "trace.jsonl",
+                "--trace-filter",
+                "loud"
+            ]),
+            Err(ArgsError::Invalid("--trace-filter", String::from("loud")))
+        );
+        assert_eq!(
+            parse(&["--day", "9", "--trace-filter", "debug"]),
+            Err(ArgsError::MissingFor("--trace", "--trace-filter"))
+        );
+        assert_eq!(
+            parse(&["--bench", "--trace", "trace.jsonl"]),
+            Err(ArgsError::Conflict("--trace", "--bench"))
+        );
+    }
+
+    #[test]
+    fn visualize() {
+        assert_eq!(
+            parse(&["--visualize", "rope.svg", "--day", "9", "--part", "2"]),
+            Ok(Command::Visualize(VisualizeArgs {
+                day: 9,
+                part: Part::Two,
+                input: Input::Default,
+                options: Vec::new(),
+                path: PathBuf::from("rope.svg"),
+                format: visual::Format::AnimatedSvg,
+                max_frames: DEFAULT_MAX_FRAMES
+            }))
+        );
+        assert!(matches!(
+            parse(&[
+                "--visualize",
+                "crt",
+                "--day",
+                "10",
+                "--visual-format",
+                "ascii",
+                "--max-frames",
+                "20",
+                "--set",
+                "screen_size=[8, 2]"
+            ]),
+            Ok(Command::Visualize(VisualizeArgs {
+                part: Part::One,
+                format: visual::Format::Ascii,
+                max_frames: 20,
+                ref options,
+                ..
+            })) if options.len() == 1
+        ));
+        assert_eq!(
+            parse(&["--visualize", "crt", "--day", "10"]),
+            Err(ArgsError::MissingFor("--visual-format", "--visualize"))
+        );
+        assert_eq!(
+            parse(&[
+                "--visualize",
+                "crt.png",
+                "--day",
+                "10",
+                "--visual-format",
+                "gif"
+            ]),
+            Err(ArgsError::Invalid("--visual-format", String::from("gif")))
+        );
+        assert_eq!(
+            parse(&["--visualize", "crt.png"]),
+            Err(ArgsError::MissingFor("--day", "--visualize"))
+        );
+        assert_eq!(
+            parse(&["--day", "10", "--max-frames", "20"]),
+            Err(ArgsError::MissingFor("--visualize", "--max-frames"))
+        );
+        assert_eq!(
+            parse(&["--visualize", "crt.png", "--day", "10", "--format", "json"]),
+            Err(ArgsError::Conflict("--visualize", "--format"))
+        );
+    }
+
+    #[test]
+    fn all() {
+        assert_eq!(
+            parse(&["--all"]),
+            Ok(Command::All(AllArgs {
+                part: None,
+                format: Format::Text,
+                jobs: None,
+                trace: None
+            }))
+        );
+        assert_eq!(
+            parse(&["--all", "--part", "2", "--format", "json", "--jobs", "4"]),
+            Ok(Command::All(AllArgs {
+                part: Some(Part::Two),
+                format: Format::Json,
+                jobs: Some(4),
+                trace: None
+            }))
+        );
+        assert_eq!(
+            parse(&["--all", "--day", "1"]),
+            Err(ArgsError::Conflict("--all", "--day"))
+        );
+        assert_eq!(
+            parse(&["--all", "--jobs", "0"]),
+            Err(ArgsError::Invalid("--jobs", String::from("0")))
+        );
+        assert_eq!(
+            parse(&["--day", "1", "--jobs", "2"]),
+            Err(ArgsError::MissingFor("--all", "--jobs"))
+        );
+    }
+
+    #[test]
+    fn check() {
+        assert_eq!(
+            parse(&["--check"]),
+            Ok(Command::Check {
+                day: None,
+                part: None
+            })
+        );
+        assert_eq!(
+            parse(&["--day", "8", "--check", "--part", "1"]),
+            Ok(Command::Check {
+                day: Some(8),
+                part: Some(Part::One)
+            })
+        );
+        assert_eq!(
+            parse(&["--check", "--input", "-"]),
+            Err(ArgsError::Conflict("--check", "--input"))
+        );
+    }
+
+    #[test]
+    fn bench() {
+        assert_eq!(
+            parse(&["--bench"]),
+            Ok(Command::Bench(BenchArgs {
+                day: None,
+                input: Input::Default,
+                iterations: DEFAULT_ITERATIONS,
+                baseline: None,
+                save_baseline: None,
+                threshold: DEFAULT_THRESHOLD,
+                options: Vec::new(),
+            }))
+        );
+        assert_eq!(
+            parse(&[
+                "--bench",
+                "--day",
+                "8",
+                "--iterations",
+                "3",
+                "--baseline",
+                "old.json",
+                "--save-baseline",
+                "new.json",
+                "--threshold",
+                "25"
+            ]),
+            Ok(Command::Bench(BenchArgs {
+                day: Some(8),
+                input: Input::Default,
+                iterations: 3,
+                baseline: Some(PathBuf::from("old.json")),
+                save_baseline: Some(PathBuf::from("new.json")),
+                threshold: 25,
+                options: Vec::new(),
+            }))
+        );
+        assert_eq!(
+            parse(&["--bench", "--input", "big.txt"]),
+            Err(ArgsError::MissingFor("--day", "--input"))
+        );
+        assert_eq!(
+            parse(&["--bench", "--iterations", "0"]),
+            Err(ArgsError::Invalid("--iterations", String::from("0")))
+        );
+        assert_eq!(
+            parse(&["--day", "1", "--iterations", "5"]),
+            Err(ArgsError::MissingFor("--bench", "--iterations"))
+        );
+        assert_eq!(
+            parse(&["--bench", "--part", "1"]),
+            Err(ArgsError::Conflict("--bench", "--part"))
+        );
+    }
+
+    #[test]
+    fn generate() {
+        assert_eq!(
+            parse(&["--generate", "--day", "3"]),
+            Ok(Command::Generate(GenerateArgs {
+                day: 3,
+                size: None,
+                seed: None
+            }))
+        );
+        assert_eq!(
+            parse(&["--generate", "--day", "8", "--size", "1000", "--seed", "42"]),
+            Ok(Command::Generate(GenerateArgs {
+                day: 8,
+                size: Some(1000),
+                seed: Some(42)
+            }))
+        );
+        assert_eq!(
+            parse(&["--generate"]),
+            Err(ArgsError::MissingFor("--day", "--generate"))
+        );
+        assert_eq!(
+            parse(&["--generate", "--day", "1", "--bench"]),
+            Err(ArgsError::Conflict("--generate", "--bench"))
+        );
+        assert_eq!(
+            parse(&["--day", "1", "--seed", "5"]),
+            Err(ArgsError::MissingFor("--generate", "--seed"))
+        );
+    }
+
+    #[test]
+    fn download() {
+        assert_eq!(
+            parse(&["--download", "--day", "11"]),
+            Ok(Command::Download(DownloadArgs {
+                day: 11,
+                force: false
+            }))
+        );
+        assert_eq!(
+            parse(&["--day", "3", "--download", "--force"]),
+            Ok(Command::Download(DownloadArgs {
+                day: 3,
+                force: true
+            }))
+        );
+        assert_eq!(
+            parse(&["--download"]),
+            Err(ArgsError::MissingFor("--day", "--download"))
+        );
+        assert_eq!(
+            parse(&["--download", "--day", "26"]),
+            Err(ArgsError::Invalid("--day", String::from("26")))
+        );
+        assert_eq!(
+            parse(&["--download", "--day", "1", "--check"]),
+            Err(ArgsError::Conflict("--download", "--check"))
+        );
+        assert_eq!(
+            parse(&["--day", "1", "--force"]),
+            Err(ArgsError::MissingFor("--download", "--force"))
+        );
+    }
+
+    #[test]
+    fn submit() {
+        assert_eq!(
+            parse(&["--submit", "--day", "5", "--part", "2"]),
+            Ok(Command::Submit(SubmitArgs {
+                day: 5,
+                part: Part::Two,
+                answer: None
+            }))
+        );
+        assert_eq!(
+            parse(&["--submit", "--day", "10", "--part", "2", "--answer", "EHPZPJGL"]),
+            Ok(Command::Submit(SubmitArgs {
+                day: 10,
+                part: Part::Two,
+                answer: Some(String::from("EHPZPJGL"))
+            }))
+        );
+        assert_eq!(
+            parse(&["--submit", "--day", "5"]),
+            Err(ArgsError::MissingFor("--part", "--submit"))
+        );
+        assert_eq!(
+            parse(&["--submit", "--part", "1"]),
+            Err(ArgsError::MissingFor("--day", "--submit"))
+        );
+        assert_eq!(
+            parse(&["--submit", "--day", "5", "--part", "1", "--download"]),
+            Err(ArgsError::Conflict("--submit", "--download"))
+        );
+        assert_eq!(
+            parse(&["--day", "5", "--answer", "CMZ"]),
+            Err(ArgsError::MissingFor("--submit", "--answer"))
+        );
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(
+            parse(&["--new", "--day", "11"]),
+            Ok(Command::New { day: 11 })
+        );
+        assert_eq!(
+            parse(&["--new"]),
+            Err(ArgsError::MissingFor("--day", "--new"))
+        );
+        assert_eq!(
+            parse(&["--new", "--day", "0"]),
+            Err(ArgsError::Invalid("--day", String::from("0")))
+        );
+        assert_eq!(
+            parse(&["--new", "--day", "11", "--download"]),
+            Err(ArgsError::Conflict("--new", "--download"))
+        );
+        assert_eq!(parse(&["new", "--day", "11"]), Ok(Command::New { day: 11 }));
+        assert_eq!(
+            parse(&["new", "--day", "11", "--check"]),
+            Err(ArgsError::Conflict("new", "--check"))
+        );
+        assert_eq!(parse(&["new"]), Err(ArgsError::MissingFor("--day", "new")));
+        assert_eq!(
+            parse(&["--day", "11", "new"]),
+            Err(ArgsError::Unknown(String::from("new")))
+        );
+        // Options of other commands are not silently ignored
+        assert_eq!(
+            parse(&["new", "--day", "11", "--size", "5"]),
+            Err(ArgsError::MissingFor("--generate", "--size"))
+        );
+        assert_eq!(
+            parse(&["--new", "--day", "11", "--answer", "42"]),
+            Err(ArgsError::MissingFor("--submit", "--answer"))
+        );
+    }
+
+    #[test]
+    fn watch() {
+        assert_eq!(
+            parse(&["--watch", "--day", "10"]),
+            Ok(Command::Watch { day: 10 })
+        );
+        assert_eq!(
+            parse(&["--watch"]),
+            Err(ArgsError::MissingFor("--day", "--watch"))
+        );
+        assert_eq!(
+            parse(&["--watch", "--day", "10", "--part", "1"]),
+            Err(ArgsError::Conflict("--watch", "--part"))
+        );
+        assert_eq!(
+            parse(&["watch", "--day", "10"]),
+            Ok(Command::Watch { day: 10 })
+        );
+        assert_eq!(
+            parse(&["watch", "--day", "10", "--input", "-"]),
+            Err(ArgsError::Conflict("watch", "--input"))
+        );
+        assert_eq!(
+            parse(&["watch", "--day", "10", "--new"]),
+            Err(ArgsError::Conflict("watch", "--new"))
+        );
+        assert_eq!(
+            parse(&["watch", "watch"]),
+            Err(ArgsError::Unknown(String::from("watch")))
+        );
+    }
+
+    #[test]
+    fn help() {
+        assert_eq!(parse(&["--day", "1", "--help"]), Ok(Command::Help));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse(&[]), Err(ArgsError::Missing("--day")));
+        assert_eq!(parse(&["--day"]), Err(ArgsError::MissingValue("--day")));
+        assert_eq!(
+            parse(&["--day", "seven"]),
+            Err(ArgsError::Invalid("--day", String::from("seven")))
+        );
+        assert_eq!(
+            parse(&["--day", "7", "--part", "3"]),
+            Err(ArgsError::Invalid("--part", String::from("3")))
+        );
+        assert_eq!(
+            parse(&["--day", "7", "--verbose"]),
+            Err(ArgsError::Unknown(String::from("--verbose")))
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use common::Timings;
+use serde::{Deserialize, Serialize};
+
+pub const STEPS: [&str; 3] = ["parse", "part1", "part2"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub mean_ns: u64,
+    pub median_ns: u64,
+    pub min_ns: u64,
+}
+
+impl Stats {
+    pub fn from_durations(durations: &[Duration]) -> Option<Stats> {
+        let mut nanos: Vec<u64> = durations
+            .iter()
+            .map(|duration| duration.as_nanos() as u64)
+            .collect();
+        nanos.sort_unstable();
+
+        let count = nanos.len() as u64;
+        let min_ns = *nanos.first()?;
+        let mean_ns = nanos.iter().sum::<u64>() / count;
+        let middle = nanos.len() / 2;
+        let median_ns = if nanos.len().is_multiple_of(2) {
+            (nanos[middle - 1] + nanos[middle]) / 2
+        } else {
+            nanos[middle]
+        };
+
+        Some(Stats {
+            mean_ns,
+            median_ns,
+            min_ns,
+        })
+    }
+
+    // In megabytes of input per second, from the mean time
+    pub fn throughput(&self, input_len: usize) -> f64 {
+        input_len as f64 / self.mean_ns.max(1) as f64 * 1e9 / 1e6
+    }
+}
+
+/// Stats of every benchmarked step, by day and then by step name.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    days: BTreeMap<u8, BTreeMap<String, Stats>>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline, String> {
+        let string = fs::read_to_string(path)
+            .map_err(|err| format!("could not read the baseline {}: {err}", path.display()))?;
+        serde_json::from_str(&string)
+            .map_err(|err| format!("invalid baseline {}: {err}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let string = serde_json::to_string_pretty(self).expect("Baseline is serializable");
+        fs::write(path, string + "\n")
+            .map_err(|err| format!("could not write the baseline {}: {err}", path.display()))
+    }
+
+    pub fn insert(&mut self, day: u8, timings: &Timings) {
+        let steps = self.days.entry(day).or_default();
+        for (step, durations) in STEPS
+            .iter()
+            .zip([&timings.parse, &timings.part1, &timings.part2])
+        {
+            if let Some(stats) = Stats::from_durations(durations) {
+                steps.insert(String::from(*step), stats);
+            }
+        }
+    }
+
+    pub fn get(&self, day: u8, step: &str) -> Option<&Stats> {
+        self.days.get(&day)?.get(step)
+    }
+}
+
+/// How much slower (positive) or faster (negative) a step got,
+/// in percent of the baseline median.
+pub fn change(current: &Stats, baseline: &Stats) -> f64 {
+    (current.median_ns as f64 / baseline.median_ns.max(1) as f64 - 1.0) * 100.0
+}
+
+pub fn print_header() {
+    println!(
+        "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}  {:>12}  Baseline",
+        "Day", "Step", "Mean", "Median", "Min", "Throughput"
+    );
+}
+
+/// Prints a day's stats, and returns whether any step regressed
+/// by more than `threshold` percent compared to the baseline.
+pub fn print_day(
+    day: u8,
+    input_len: usize,
+    current: &Baseline,
+    baseline: Option<&Baseline>,
+    threshold: f64,
+) -> bool {
+    let mut regressed = false;
+
+    for step in STEPS {
+        let Some(stats) = current.get(day, step) else {
+            continue;
+        };
+
+        let comparison = match baseline.and_then(|baseline| baseline.get(day, step)) {
+            Some(previous) => {
+                let change = change(stats, previous);
+                if change > threshold {
+                    regressed = true;
+                    format!("{change:+.1}% regression")
+                } else {
+                    format!("{change:+.1}%")
+                }
+            }
+            None => String::from("-"),
+        };
+
+        println!(
+            "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}  {:>7.1} MB/s  {}",
+            day,
+            step,
+            format!("{:.3?}", Duration::from_nanos(stats.mean_ns)),
+            format!("{:.3?}", Duration::from_nanos(stats.median_ns)),
+            format!("{:.3?}", Duration::from_nanos(stats.min_ns)),
+            stats.throughput(input_len),
+            comparison
+        );
+    }
+
+    regressed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values
+            .iter()
+            .map(|&value| Duration::from_nanos(value))
+            .collect()
+    }
+
+    #[test]
+    fn stats() {
+        assert_eq!(
+            Stats::from_durations(&nanos(&[30, 10, 20])),
+            Some(Stats {
+                mean_ns: 20,
+                median_ns: 20,
+                min_ns: 10
+            })
+        );
+        assert_eq!(
+            Stats::from_durations(&nanos(&[40, 10, 20, 10])),
+            Some(Stats {
+                mean_ns: 20,
+                median_ns: 15,
+                min_ns: 10
+            })
+        );
+        assert_eq!(Stats::from_durations(&[]), None);
+    }
+
+    #[test]
+    fn throughput() {
+        let stats = Stats {
+            mean_ns: 1_000_000,
+            median_ns: 1_000_000,
+            min_ns: 1_000_000,
+        };
+        assert_eq!(stats.throughput(2_000_000), 2000.0);
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        let timings = Timings {
+            parse: nanos(&[100, 200]),
+            part1: nanos(&[10]),
+            part2: nanos(&[1000, 3000, 2000]),
+        };
+        baseline.insert(7, &timings);
+
+        assert_eq!(baseline.get(7, "part1").unwrap().median_ns, 10);
+        assert_eq!(baseline.get(7, "part2").unwrap().median_ns, 2000);
+        assert!(baseline.get(8, "part1").is_none());
+
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap(), baseline);
+    }
+
+    #[test]
+    fn changes() {
+        let stats = |median_ns| Stats {
+            mean_ns: median_ns,
+            median_ns,
+            min_ns: median_ns,
+        };
+        assert_eq!(change(&stats(150), &stats(100)), 50.0);
+        assert_eq!(change(&stats(50), &stats(100)), -50.0);
+    }
+}
@@ -0,0 +1,196 @@
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+
+use common::Part;
+
+use crate::answers::Registry;
+use crate::days::Day;
+
+// Inputs every day is expected to have, even without a recorded answer
+const INPUTS: [&str; 2] = ["test-input", "input"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    // The answer differs from the known good one
+    Regression,
+    // No answer at all, because of a missing input or a parse error
+    Fail,
+    // Nothing to compare the answer with
+    Unknown,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let status = match self {
+            Status::Pass => "pass",
+            Status::Regression => "regression",
+            Status::Fail => "fail",
+            Status::Unknown => "unknown",
+        };
+        // Padding is applied by the table
+        f.pad(status)
+    }
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub day: u8,
+    pub input: String,
+    pub part: Part,
+    pub status: Status,
+    pub answer: Result<Option<String>, String>,
+    pub expected: Option<String>,
+}
+
+impl Outcome {
+    pub fn is_ok(&self) -> bool {
+        matches!(self.status, Status::Pass | Status::Unknown)
+    }
+
+    fn describe_answer(&self) -> String {
+        match &self.answer {
+            Ok(Some(answer)) => summary(answer),
+            Ok(None) => String::from("no answer"),
+            Err(err) => err.clone(),
+        }
+    }
+}
+
+pub fn check(day: &Day, parts: &[Part]) -> Result<Vec<Outcome>, String> {
+    let registry = Registry::load(&day.answers_file())?;
+
+    let mut inputs: Vec<&str> = INPUTS.to_vec();
+    for input in registry.inputs() {
+        if !inputs.contains(&input) {
+            inputs.push(input);
+        }
+    }
+
+    let mut outcomes = Vec::new();
+
+    for input in inputs {
+        let expected = registry.get(input);
+        let answers = File::open(day.data_file(input))
+            .map_err(|err| format!("could not read the input: {err}"))
+            .and_then(|file| {
+                (day.solve)(&mut BufReader::new(file), parts).map_err(|err| err.to_string())
+            });
+
+        for (i, &part) in parts.iter().enumerate() {
+            let expected = expected.and_then(|expected| expected.get(part));
+            let answer = match &answers {
+                Ok(answers) => Ok(answers[i].1.clone()),
+                Err(err) => Err(err.clone()),
+            };
+
+            outcomes.push(Outcome {
+                day: day.number,
+                input: String::from(input),
+                part,
+                status: status(&answer, expected),
+                answer,
+                expected: expected.map(String::from),
+            });
+        }
+    }
+
+    Ok(outcomes)
+}
+
+fn status(answer: &Result<Option<String>, String>, expected: Option<&str>) -> Status {
+    match (answer, expected) {
+        (Ok(Some(answer)), Some(expected)) if answer.trim_end() == expected.trim_end() => {
+            Status::Pass
+        }
+        (Ok(Some(_)), Some(_)) => Status::Regression,
+        (Ok(Some(_)), None) => Status::Unknown,
+        (Ok(None), None) => Status::Unknown,
+        (Ok(None), Some(_)) | (Err(_), _) => Status::Fail,
+    }
+}
+
+// Multi-line answers, like rendered screens, only show their first line
+pub fn summary(answer: &str) -> String {
+    let mut lines = answer.trim_matches('\n').lines();
+    let first = lines.next().unwrap_or_default();
+    match lines.count() {
+        0 => String::from(first),
+        more => format!("{first} (+{more} lines)"),
+    }
+}
+
+pub fn print_table(outcomes: &[Outcome]) {
+    println!(
+        "{:>3}  {:<12}  {:>4}  {:<10}  Answer",
+        "Day", "Input", "Part", "Status"
+    );
+
+    for outcome in outcomes {
+        println!(
+            "{:>3}  {:<12}  {:>4}  {:<10}  {}",
+            outcome.day,
+            outcome.input,
+            outcome.part.number(),
+            outcome.status,
+            outcome.describe_answer()
+        );
+    }
+
+    for outcome in outcomes.iter().filter(|outcome| !outcome.is_ok()) {
+        println!(
+            "\nDay {}, {}, part {}: {}",
+            outcome.day, outcome.input, outcome.part, outcome.status
+        );
+        if let Some(expected) = &outcome.expected {
+            println!("expected: {}", summary(expected));
+        }
+        println!("got:      {}", outcome.describe_answer());
+    }
+
+    let passed = outcomes
+        .iter()
+        .filter(|outcome| outcome.status == Status::Pass)
+        .count();
+    let failed = outcomes.iter().filter(|outcome| !outcome.is_ok()).count();
+    println!(
+        "\n{passed} passed, {failed} failed, {} unknown",
+        outcomes.len() - passed - failed
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statuses() {
+        let answer = Ok(Some(String::from("42")));
+        assert_eq!(status(&answer, Some("42")), Status::Pass);
+        assert_eq!(status(&answer, Some("43")), Status::Regression);
+        assert_eq!(status(&answer, None), Status::Unknown);
+
+        let render = Ok(Some(String::from("#.#\n.#.\n")));
+        assert_eq!(status(&render, Some("#.#\n.#.")), Status::Pass);
+
+        assert_eq!(status(&Ok(None), Some("42")), Status::Fail);
+        assert_eq!(status(&Ok(None), None), Status::Unknown);
+        assert_eq!(status(&Err(String::from("bad")), None), Status::Fail);
+    }
+
+    #[test]
+    fn summaries() {
+        assert_eq!(summary("CMZ"), "CMZ");
+        assert_eq!(summary("##..\n.##.\n..##\n"), "##.. (+2 lines)");
+    }
+
+    #[test]
+    fn every_day_passes() {
+        for day in crate::testing::solved_days() {
+            for outcome in check(day, &Part::BOTH).unwrap() {
+                assert_eq!(outcome.status, Status::Pass, "{outcome:?}");
+            }
+        }
+    }
+}
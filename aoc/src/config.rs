@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::days;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_YEAR: u16 = 2022;
+pub const DEFAULT_MIN_INTERVAL: u64 = 5;
+
+/// Settings of the runner, from `aoc.toml` at the workspace root,
+/// or from the file at `$AOC_CONFIG`.
+///
+/// The file is optional, every setting having a default.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Config {
+    pub remote: Remote,
+    /// Options of the puzzles, in a `[dayNN]` table for each day.
+    #[serde(flatten)]
+    pub days: BTreeMap<String, toml::Table>,
+}
+
+/// How to reach the Advent of Code website.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Remote {
+    /// Value of the `session` cookie of a logged in browser.
+    pub session: Option<String>,
+    /// Another server can stand in for the website, like in tests.
+    pub base_url: String,
+    pub year: u16,
+    /// Seconds to wait at least between two requests.
+    pub min_interval: u64,
+}
+
+impl Default for Remote {
+    fn default() -> Remote {
+        Remote {
+            session: None,
+            base_url: String::from(DEFAULT_BASE_URL),
+            year: DEFAULT_YEAR,
+            min_interval: DEFAULT_MIN_INTERVAL,
+        }
+    }
+}
+
+impl Remote {
+    pub fn min_interval(&self) -> Duration {
+        Duration::from_secs(self.min_interval)
+    }
+}
+
+impl Config {
+    pub fn path() -> PathBuf {
+        match env::var_os("AOC_CONFIG") {
+            Some(path) => PathBuf::from(path),
+            None => days::root().join("aoc.toml"),
+        }
+    }
+
+    pub fn from_toml(string: &str) -> Result<Config, String> {
+        let config: Config = toml::from_str(string).map_err(|err| err.to_string())?;
+
+        // Any other table is taken for a day's, so tell typos apart
+        for name in config.days.keys() {
+            let number = name.strip_prefix("day").and_then(|number| {
+                let number: u8 = number.parse().ok()?;
+                (name.len() == 5 && (1..=25).contains(&number)).then_some(number)
+            });
+            if number.is_none() {
+                return Err(format!(
+                    "unknown table `[{name}]`, expected `[remote]` or `[dayNN]`"
+                ));
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Options of the puzzle of day `number`, by name.
+    pub fn options(&self, number: u8) -> Map<String, Value> {
+        let Some(table) = self.days.get(&format!("day{number:02}")) else {
+            return Map::new();
+        };
+        table
+            .iter()
+            .map(|(name, value)| {
+                let value = serde_json::to_value(value).expect("TOML values are serializable");
+                (name.clone(), value)
+            })
+            .collect()
+    }
+
+    pub fn load(path: &Path) -> Result<Config, String> {
+        match fs::read_to_string(path) {
+            Ok(string) => Config::from_toml(&string)
+                .map_err(|err| format!("invalid config file {}: {err}", path.display())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(err) => Err(format!("could not read {}: {err}", path.display())),
+        }
+    }
+}
+
+/// An option's value as written on the command line, like in TOML,
+/// where a bare word is taken as a string.
+pub fn option_value(value: &str) -> Value {
+    match format!("value = {value}").parse::<toml::Table>() {
+        Ok(mut table) => {
+            let value = table.remove("value").expect("The table has a value");
+            serde_json::to_value(value).expect("TOML values are serializable")
+        }
+        Err(_) => Value::from(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_toml() {
+        let config = Config::from_toml(
+            r#"
+[remote]
+session = "53616c746564"
+base_url = "http://127.0.0.1:8080"
+min_interval = 0
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.remote,
+            Remote {
+                session: Some(String::from("53616c746564")),
+                base_url: String::from("http://127.0.0.1:8080"),
+                year: DEFAULT_YEAR,
+                min_interval: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn options() {
+        let config = Config::from_toml(
+            r#"
+[day07]
+max_size = 1000
+
+[day10]
+cycles = [20, 60]
+screen_size = [8, 2]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.remote, Remote::default());
+        assert_eq!(Value::from(config.options(7)), json!({ "max_size": 1000 }));
+        assert_eq!(
+            Value::from(config.options(10)),
+            json!({ "cycles": [20, 60], "screen_size": [8, 2] })
+        );
+        assert!(config.options(1).is_empty());
+
+        assert!(Config::from_toml("[day7]\nmax_size = 1").is_err());
+        assert!(Config::from_toml("[day26]\nmax_size = 1").is_err());
+        assert!(Config::from_toml("[remot]\nsession = \"typo\"").is_err());
+    }
+
+    #[test]
+    fn option_values() {
+        assert_eq!(option_value("1000"), json!(1000));
+        assert_eq!(option_value("5e7"), json!(5e7));
+        assert_eq!(option_value("[40, 6]"), json!([40, 6]));
+        assert_eq!(option_value("\"a b\""), json!("a b"));
+        assert_eq!(option_value("abc"), json!("abc"));
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(Config::from_toml("").unwrap(), Config::default());
+        assert_eq!(
+            Config::load(Path::new("does/not/exist.toml")),
+            Ok(Config::default())
+        );
+        assert!(Config::from_toml("[remote]\nsesion = \"typo\"").is_err());
+    }
+}
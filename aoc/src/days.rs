@@ -0,0 +1,182 @@
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use common::{Animation, Answers, ParseError, Part, Run, Solution, Timings};
+use serde_json::Value;
+
+// Drawing a part with options returned by `Day::options`, in at most so
+// many frames
+type Animate = fn(&mut dyn BufRead, Part, &Value, usize) -> Result<Option<Animation>, ParseError>;
+
+pub struct Day {
+    pub number: u8,
+    // Every option of the puzzle, from an object of the ones to change
+    pub options: fn(&Value) -> Result<Value, String>,
+    // With the puzzle's own options, like for checking and submitting
+    pub solve: fn(&mut dyn BufRead, &[Part]) -> Result<Answers, ParseError>,
+    // With options returned by `options`
+    pub run: fn(&mut dyn BufRead, &[Part], &Value) -> Result<Run, ParseError>,
+    pub bench: fn(&str, usize, &Value) -> Result<Timings, ParseError>,
+    // None for a part the day does not draw
+    pub animate: Animate,
+    pub generate: fn(usize, u64) -> String,
+    // Size of the generated inputs, unless another one is asked for
+    pub size: usize,
+}
+
+fn options<S: Solution>(overrides: &Value) -> Result<Value, String> {
+    let options = common::options::<S>(overrides)?;
+    Ok(serde_json::to_value(options).expect("Options are serializable"))
+}
+
+fn checked<S: Solution>(options: &Value) -> S::Options {
+    common::options::<S>(options).expect("Options were checked by `Day::options`")
+}
+
+macro_rules! day {
+    ($number:expr, $solution:ty) => {
+        Day {
+            number: $number,
+            options: options::<$solution>,
+            solve: |input, parts| common::solve::<$solution>(input, parts, &Default::default()),
+            run: |input, parts, options| {
+                common::run::<$solution>(input, parts, &checked::<$solution>(options))
+            },
+            bench: |input, iterations, options| {
+                common::bench::<$solution>(input, iterations, &checked::<$solution>(options))
+            },
+            animate: |input, part, options, max_frames| {
+                common::animate::<$solution>(
+                    input,
+                    part,
+                    &checked::<$solution>(options),
+                    max_frames,
+                )
+            },
+            generate: common::generate::<$solution>,
+            size: <$solution as common::Generator>::SIZE,
+        }
+    };
+}
+
+pub const DAYS: [Day; 10] = [
+    day!(1, day01::Day01),
+    day!(2, day02::Day02),
+    day!(3, day03::Day03),
+    day!(4, day04::Day04),
+    day!(5, day05::Day05),
+    day!(6, day06::Day06),
+    day!(7, day07::Day07),
+    day!(8, day08::Day08),
+    day!(9, day09::Day09),
+    day!(10, day10::Day10),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+pub fn root() -> &'static Path {
+    // The runner lives next to the day crates, at the workspace root
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("Runner crate is inside the workspace")
+}
+
+// Also for days that are not solved yet, like when downloading their input
+pub fn dir(number: u8) -> PathBuf {
+    root().join(format!("day{number:02}"))
+}
+
+pub fn default_input(number: u8) -> PathBuf {
+    dir(number).join("data").join("input.txt")
+}
+
+pub fn answers_file(number: u8) -> PathBuf {
+    dir(number).join("data").join("answers.toml")
+}
+
+impl Day {
+    pub fn dir(&self) -> PathBuf {
+        dir(self.number)
+    }
+
+    // An input of the day, by its name in the answers registry
+    pub fn data_file(&self, input: &str) -> PathBuf {
+        self.dir().join("data").join(format!("{input}.txt"))
+    }
+
+    pub fn default_input(&self) -> PathBuf {
+        default_input(self.number)
+    }
+
+    pub fn answers_file(&self) -> PathBuf {
+        answers_file(self.number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn find_days() {
+        assert_eq!(find(1).unwrap().number, 1);
+        assert_eq!(find(10).unwrap().number, 10);
+        assert!(find(0).is_none());
+        assert!(find(25).is_none());
+    }
+
+    #[test]
+    fn options() {
+        let day = find(7).unwrap();
+        let options = (day.options)(&json!({ "max_size": 1000 })).unwrap();
+        assert_eq!(
+            options,
+            json!({ "max_size": 1000, "total_size": 70_000_000, "needed_size": 30_000_000 })
+        );
+        assert!((day.options)(&json!({ "max_sise": 1000 })).is_err());
+        assert!((find(2).unwrap().options)(&json!({ "max_size": 1000 })).is_err());
+
+        let input = std::fs::read_to_string(day.data_file("test-input")).unwrap();
+        let run = (day.run)(&mut input.as_bytes(), &[Part::One], &options).unwrap();
+        assert_eq!(run.answers, [(Part::One, Some(String::from("584")))]);
+    }
+
+    #[test]
+    fn animations() {
+        for number in [5, 8, 9, 10] {
+            let day = find(number).unwrap();
+            let input = std::fs::read_to_string(day.data_file("test-input")).unwrap();
+            let options = (day.options)(&Value::Null).unwrap();
+            for part in Part::BOTH {
+                let animation = (day.animate)(&mut input.as_bytes(), part, &options, 3).unwrap();
+                assert!(
+                    animation.is_some_and(|animation| (1..=3).contains(&animation.frames.len()))
+                );
+            }
+        }
+        let day = find(1).unwrap();
+        let animation = (day.animate)(&mut "1\n".as_bytes(), Part::One, &json!({}), 0).unwrap();
+        assert!(animation.is_none());
+    }
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for day in &DAYS {
+            let input = (day.generate)(10, 1);
+            let answers = (day.solve)(&mut input.as_bytes(), &Part::BOTH).unwrap();
+            for (part, answer) in answers {
+                assert!(answer.is_some(), "day {}, part {part}", day.number);
+            }
+        }
+    }
+
+    #[test]
+    fn default_inputs_exist() {
+        for day in &DAYS {
+            assert!(day.default_input().is_file(), "day {}", day.number);
+        }
+    }
+}
@@ -0,0 +1,420 @@
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::process::ExitCode;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+mod all;
+mod answers;
+mod args;
+mod bench;
+mod check;
+mod config;
+mod days;
+mod remote;
+mod report;
+mod scaffold;
+#[cfg(test)]
+mod testing;
+mod watch;
+
+use answers::{Known, Registry};
+use args::{
+    AllArgs, Args, BenchArgs, Command, DownloadArgs, Format, GenerateArgs, Input, Overrides,
+    SubmitArgs, Trace, VisualizeArgs,
+};
+use common::{visual, Part};
+use config::Config;
+use remote::{Client, Fetched, Throttle, Verdict};
+use serde_json::Value;
+
+fn main() -> ExitCode {
+    let command = match Command::parse(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{}", args::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let result = match command {
+        Command::Help => {
+            println!("{}", args::USAGE);
+            Ok(())
+        }
+        Command::Solve(args) => traced(&args.trace, || solve(&args)),
+        Command::All(args) => traced(&args.trace, || all(&args)),
+        Command::Check { day, part } => check(day, part),
+        Command::Bench(args) => bench(&args),
+        Command::Visualize(args) => visualize(&args),
+        Command::Generate(args) => generate(&args),
+        Command::Download(args) => download(&args),
+        Command::Submit(args) => submit(&args),
+        Command::New { day } => new_day(day),
+        Command::Watch { day } => watch::watch(day),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn find_day(number: u8) -> Result<&'static days::Day, String> {
+    days::find(number).ok_or(format!("day {number} is not solved yet"))
+}
+
+fn parts(part: Option<Part>) -> Vec<Part> {
+    match part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    }
+}
+
+// Read as it is parsed, without holding the whole input in memory first
+fn open_input(day: &days::Day, input: &Input) -> Result<Box<dyn BufRead>, String> {
+    match input {
+        Input::Stdin => Ok(Box::new(io::stdin().lock())),
+        Input::Path(path) => match File::open(path) {
+            Ok(file) => Ok(Box::new(BufReader::new(file))),
+            Err(err) => Err(format!(
+                "could not read the input at {}: {err}",
+                path.display()
+            )),
+        },
+        Input::Default => {
+            let path = day.default_input();
+            match File::open(&path) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(err) => Err(format!(
+                    "could not read the input for day {} at {}: {err}\n\
+                     (use `--input <PATH>` to read it from somewhere else)",
+                    day.number,
+                    path.display()
+                )),
+            }
+        }
+    }
+}
+
+fn read_input(day: &days::Day, input: &Input) -> Result<String, String> {
+    let mut string = String::new();
+    open_input(day, input)?
+        .read_to_string(&mut string)
+        .map_err(|err| format!("could not read the input for day {}: {err}", day.number))?;
+    Ok(string)
+}
+
+// Options of the day's `aoc.toml` table, changed by the command line's
+fn options(config: &Config, day: &days::Day, overrides: &Overrides) -> Result<Value, String> {
+    let mut options = config.options(day.number);
+    for (name, value) in overrides {
+        options.insert(name.clone(), config::option_value(value));
+    }
+    (day.options)(&Value::Object(options))
+        .map_err(|err| format!("invalid options for day {}: {err}", day.number))
+}
+
+// Runs `command` with the solvers' events written to the trace's file, if any
+fn traced(
+    trace: &Option<Trace>,
+    command: impl FnOnce() -> Result<(), String>,
+) -> Result<(), String> {
+    let Some(trace) = trace else {
+        return command();
+    };
+
+    let file = File::create(&trace.path)
+        .map_err(|err| format!("could not create {}: {err}", trace.path.display()))?;
+    common::trace::install(trace.filter.clone(), BufWriter::new(file));
+    let result = command();
+    common::trace::finish();
+    result
+}
+
+fn solve(args: &Args) -> Result<(), String> {
+    let day = find_day(args.day)?;
+    let options = options(&Config::load(&Config::path())?, day, &args.options)?;
+    let mut input = open_input(day, &args.input)?;
+
+    let run = (day.run)(&mut input, &parts(args.part), &options)
+        .map_err(|err| format!("could not parse the input for day {}: {err}", day.number))?;
+
+    match args.format {
+        Format::Text => {
+            for (part, answer) in run.answers {
+                common::print_answer(part, answer.as_deref());
+            }
+        }
+        Format::Json => {
+            let document = report::document(day.number, &run, &options);
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&document).expect("JSON values are serializable")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn all(args: &AllArgs) -> Result<(), String> {
+    let config = Config::load(&Config::path())?;
+    let days: Vec<&'static days::Day> = days::DAYS.iter().collect();
+    let parts = parts(args.part);
+
+    let start = Instant::now();
+    let runs = all::run_all(&days, args.jobs.unwrap_or_else(all::default_jobs), |day| {
+        let options = options(&config, day, &Overrides::new())?;
+        let path = day.default_input();
+        let file = File::open(&path)
+            .map_err(|err| format!("could not read the input at {}: {err}", path.display()))?;
+        let run = (day.run)(&mut BufReader::new(file), &parts, &options)
+            .map_err(|err| format!("could not parse the input: {err}"))?;
+        Ok((run, options))
+    });
+    let elapsed = start.elapsed();
+
+    match args.format {
+        Format::Text => all::print_table(&runs, elapsed),
+        Format::Json => {
+            let document = all::document(&runs, elapsed);
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&document).expect("JSON values are serializable")
+            );
+        }
+    }
+
+    if runs.iter().all(all::DayRun::is_ok) {
+        Ok(())
+    } else {
+        Err(String::from("some days could not be solved"))
+    }
+}
+
+fn visualize(args: &VisualizeArgs) -> Result<(), String> {
+    let day = find_day(args.day)?;
+    let options = options(&Config::load(&Config::path())?, day, &args.options)?;
+    let mut input = open_input(day, &args.input)?;
+
+    let animation = (day.animate)(&mut input, args.part, &options, args.max_frames)
+        .map_err(|err| format!("could not parse the input for day {}: {err}", day.number))?
+        .ok_or_else(|| format!("day {} cannot draw part {}", day.number, args.part))?;
+
+    let path = &args.path;
+    let mut out = BufWriter::new(
+        File::create(path).map_err(|err| format!("could not create {}: {err}", path.display()))?,
+    );
+    visual::export(&animation, args.format, &mut out)
+        .and_then(|()| out.flush())
+        .map_err(|err| format!("could not write {}: {err}", path.display()))?;
+
+    println!(
+        "Drew {} frames of day {} part {} in {}",
+        animation.frames.len(),
+        day.number,
+        args.part,
+        path.display()
+    );
+    Ok(())
+}
+
+fn check(day: Option<u8>, part: Option<Part>) -> Result<(), String> {
+    let days = match day {
+        Some(number) => vec![find_day(number)?],
+        None => days::DAYS.iter().collect(),
+    };
+
+    let mut outcomes = Vec::new();
+    for day in days {
+        outcomes.extend(check::check(day, &parts(part))?);
+    }
+
+    check::print_table(&outcomes);
+
+    if outcomes.iter().all(check::Outcome::is_ok) {
+        Ok(())
+    } else {
+        Err(String::from("some answers are not the known good ones"))
+    }
+}
+
+fn bench(args: &BenchArgs) -> Result<(), String> {
+    let days = match args.day {
+        Some(number) => vec![find_day(number)?],
+        None => days::DAYS.iter().collect(),
+    };
+    let baseline = match &args.baseline {
+        Some(path) => Some(bench::Baseline::load(path)?),
+        None => None,
+    };
+
+    let config = Config::load(&Config::path())?;
+
+    let mut current = bench::Baseline::default();
+    let mut regressed = false;
+
+    bench::print_header();
+    for day in days {
+        let options = options(&config, day, &args.options)?;
+        let input = read_input(day, &args.input)?;
+        let timings = (day.bench)(&input, args.iterations, &options)
+            .map_err(|err| format!("could not parse the input for day {}: {err}", day.number))?;
+
+        current.insert(day.number, &timings);
+        regressed |= bench::print_day(
+            day.number,
+            input.len(),
+            &current,
+            baseline.as_ref(),
+            args.threshold as f64,
+        );
+    }
+
+    if let Some(path) = &args.save_baseline {
+        current.save(path)?;
+    }
+
+    if regressed {
+        Err(format!(
+            "some steps are more than {}% slower than the baseline",
+            args.threshold
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn generate(args: &GenerateArgs) -> Result<(), String> {
+    let day = find_day(args.day)?;
+    let seed = args.seed.unwrap_or_else(|| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System clock is after 1970");
+        now.as_nanos() as u64
+    });
+
+    // On stderr, so the input can be redirected to a file as is
+    eprintln!("seed: {seed}");
+    print!("{}", (day.generate)(args.size.unwrap_or(day.size), seed));
+
+    Ok(())
+}
+
+fn client() -> Result<Client, String> {
+    let config = Config::load(&Config::path())?;
+    let stamp = days::root().join(".aoc").join("last-request");
+    let throttle = Throttle::new(stamp, config.remote.min_interval());
+    Ok(Client::new(&config.remote, throttle))
+}
+
+fn download(args: &DownloadArgs) -> Result<(), String> {
+    let path = days::default_input(args.day);
+    match remote::fetch_input(&client()?, args.day, &path, args.force)? {
+        Fetched::Cached => println!(
+            "The input of day {} is already at {} (use `--force` to download it again)",
+            args.day,
+            path.display()
+        ),
+        Fetched::Downloaded => println!(
+            "Downloaded the input of day {} to {}",
+            args.day,
+            path.display()
+        ),
+    }
+
+    Ok(())
+}
+
+fn solved_answer(args: &SubmitArgs) -> Result<String, String> {
+    let day = find_day(args.day)?;
+    let mut input = open_input(day, &Input::Default)?;
+    let answers = (day.solve)(&mut input, &[args.part])
+        .map_err(|err| format!("could not parse the input for day {}: {err}", day.number))?;
+
+    match answers.into_iter().next() {
+        Some((_, Some(answer))) if answer.contains('\n') => Err(format!(
+            "the answer of day {} part {} is a drawing: read it, \
+             then give it with `--answer <ANSWER>`\n{answer}",
+            args.day, args.part
+        )),
+        Some((_, Some(answer))) => Ok(answer),
+        _ => Err(format!("day {} part {} has no answer", args.day, args.part)),
+    }
+}
+
+fn submit(args: &SubmitArgs) -> Result<(), String> {
+    let answer = match &args.answer {
+        Some(answer) => answer.trim().to_string(),
+        None => solved_answer(args)?,
+    };
+    let path = days::answers_file(args.day);
+    let registry = Registry::load(&path)?;
+
+    let (day, part) = (args.day, args.part);
+    match registry.known("input", part, &answer) {
+        Some(Known::Accepted) => {
+            println!("{answer} is already known to be the right answer of day {day} part {part}");
+            return Ok(());
+        }
+        Some(Known::OtherAccepted(accepted)) => {
+            // Drawn answers are recorded as drawn, not as the letters submitted
+            return Err(format!(
+                "day {day} part {part} is already solved, with another answer than {answer}:\n\
+                 {}",
+                accepted.trim_end()
+            ));
+        }
+        Some(Known::Rejected(rejection, rejected)) => {
+            return Err(format!(
+                "{answer} cannot be the right answer of day {day} part {part}, \
+                 {rejected} was already rejected as {rejection}"
+            ));
+        }
+        None => {}
+    }
+
+    match client()?.answer(day, part, &answer)? {
+        Verdict::Correct => {
+            Registry::record(&path, "input", part, &answer, None)?;
+            println!("{answer} is the right answer of day {day} part {part}!");
+            Ok(())
+        }
+        Verdict::Rejected(rejection) => {
+            Registry::record(&path, "input", part, &answer, Some(rejection))?;
+            Err(format!(
+                "{answer} is not the right answer of day {day} part {part}: {rejection}"
+            ))
+        }
+        Verdict::Wait(duration) => Err(format!(
+            "an answer was submitted too recently, wait {}s before trying again",
+            duration.as_secs()
+        )),
+        Verdict::AlreadySolved => Err(format!(
+            "day {day} part {part} is already solved, or its first part is not: \
+             add its answer to {} to check it locally",
+            path.display()
+        )),
+        Verdict::Unknown => Err(String::from(
+            "could not find the verdict in the page the website sent back",
+        )),
+    }
+}
+
+fn new_day(number: u8) -> Result<(), String> {
+    let root = days::root();
+    for path in scaffold::new_day(root, number)? {
+        let path = path.strip_prefix(root).unwrap_or(&path);
+        println!("{}", path.display());
+    }
+
+    println!(
+        "\nDay {number} is ready: paste the example into day{number:02}/data/test-input.txt, \
+         then download the input with `--download --day {number}`"
+    );
+    Ok(())
+}
@@ -0,0 +1,417 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use common::Part;
+
+use crate::answers::Rejection;
+use crate::config::Remote;
+
+const USER_AGENT: &str = concat!("aoc-2022-runner/", env!("CARGO_PKG_VERSION"));
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Keeps requests at least some time apart, even across runs, by
+/// remembering when the last one was sent in a file.
+#[derive(Debug)]
+pub struct Throttle {
+    stamp: PathBuf,
+    interval: Duration,
+}
+
+impl Throttle {
+    pub fn new(stamp: PathBuf, interval: Duration) -> Throttle {
+        Throttle { stamp, interval }
+    }
+
+    fn last_request(&self) -> Option<SystemTime> {
+        let millis = fs::read_to_string(&self.stamp).ok()?.trim().parse().ok()?;
+        Some(UNIX_EPOCH + Duration::from_millis(millis))
+    }
+
+    /// Sleeps until the next request can be sent, and marks it as sent.
+    pub fn wait(&self) -> Result<(), String> {
+        if let Some(last) = self.last_request() {
+            // A last request in the future, after a clock change, is forgotten
+            let elapsed = last.elapsed().unwrap_or(self.interval);
+            if let Some(remaining) = self.interval.checked_sub(elapsed) {
+                thread::sleep(remaining);
+            }
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System clock is after 1970");
+        if let Some(dir) = self.stamp.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|err| format!("could not create {}: {err}", dir.display()))?;
+        }
+        fs::write(&self.stamp, now.as_millis().to_string())
+            .map_err(|err| format!("could not write {}: {err}", self.stamp.display()))
+    }
+}
+
+/// What the website said about a submitted answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Rejected(Rejection),
+    // Answers cannot be submitted again before this is over
+    Wait(Duration),
+    // The part is solved already, so there is nothing to submit anymore
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    /// Finds the verdict in the page answering a submission.
+    pub fn from_page(page: &str) -> Verdict {
+        if page.contains("That's the right answer") {
+            Verdict::Correct
+        } else if page.contains("That's not the right answer") {
+            Verdict::Rejected(if page.contains("your answer is too high") {
+                Rejection::TooHigh
+            } else if page.contains("your answer is too low") {
+                Rejection::TooLow
+            } else {
+                Rejection::Wrong
+            })
+        } else if page.contains("You gave an answer too recently") {
+            Verdict::Wait(left_to_wait(page).unwrap_or(Duration::from_secs(60)))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown
+        }
+    }
+}
+
+// From "You have 1m 24s left to wait."
+fn left_to_wait(page: &str) -> Option<Duration> {
+    let start = page.find("You have ")? + "You have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+
+    page[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let unit = match amount.chars().last()? {
+                'h' => 3600,
+                'm' => 60,
+                's' => 1,
+                _ => return None,
+            };
+            let value: u64 = amount[..amount.len() - 1].parse().ok()?;
+            Some(value * unit)
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Talks to the Advent of Code website, or to whatever stands in for it
+/// at the configured base URL.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    year: u16,
+    session: Option<String>,
+    throttle: Throttle,
+}
+
+impl Client {
+    pub fn new(remote: &Remote, throttle: Throttle) -> Client {
+        Client {
+            agent: ureq::AgentBuilder::new()
+                .timeout(TIMEOUT)
+                .user_agent(USER_AGENT)
+                .build(),
+            base_url: remote.base_url.trim_end_matches('/').to_string(),
+            year: remote.year,
+            session: remote.session.clone(),
+            throttle,
+        }
+    }
+
+    // Only needed once something is actually requested
+    fn cookie(&self) -> Result<String, String> {
+        let session = self.session.as_ref().ok_or(
+            "no session token configured: set `session` in the `[remote]` \
+             section of aoc.toml to the `session` cookie of a logged in browser",
+        )?;
+        Ok(format!("session={session}"))
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{day}", self.base_url, self.year)
+    }
+
+    fn refused(err: ureq::Error, what: &str) -> String {
+        match err {
+            ureq::Error::Status(400, _) | ureq::Error::Status(401, _) => {
+                String::from("the session token was refused, it may have expired")
+            }
+            err => format!("could not {what}: {err}"),
+        }
+    }
+
+    /// The input of the logged in user for `day`.
+    pub fn input(&self, day: u8) -> Result<String, String> {
+        let cookie = self.cookie()?;
+        self.throttle.wait()?;
+
+        let url = format!("{}/input", self.day_url(day));
+        let response =
+            self.agent
+                .get(&url)
+                .set("Cookie", &cookie)
+                .call()
+                .map_err(|err| match err {
+                    ureq::Error::Status(404, _) => {
+                        format!("the input of day {day} is not available yet")
+                    }
+                    err => Client::refused(err, &format!("download the input of day {day}")),
+                })?;
+
+        response
+            .into_string()
+            .map_err(|err| format!("could not read the input of day {day}: {err}"))
+    }
+
+    /// Submits `answer` to `part` of `day`, for the logged in user.
+    pub fn answer(&self, day: u8, part: Part, answer: &str) -> Result<Verdict, String> {
+        let cookie = self.cookie()?;
+        self.throttle.wait()?;
+
+        let url = format!("{}/answer", self.day_url(day));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &cookie)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|err| Client::refused(err, &format!("submit the answer of day {day}")))?;
+
+        let page = response
+            .into_string()
+            .map_err(|err| format!("could not read the verdict on day {day}: {err}"))?;
+        Ok(Verdict::from_page(&page))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// Makes sure the input of `day` is at `path`, only downloading it when
+/// it is not there yet or when `force` is set.
+pub fn fetch_input(client: &Client, day: u8, path: &Path, force: bool) -> Result<Fetched, String> {
+    // An empty input is only a placeholder, like a new day's
+    let cached = fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0);
+    if !force && cached {
+        return Ok(Fetched::Cached);
+    }
+
+    let input = client.input(day)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("could not create {}: {err}", dir.display()))?;
+    }
+    fs::write(path, input).map_err(|err| format!("could not write {}: {err}", path.display()))?;
+
+    Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::scratch_dir;
+    use std::sync::mpsc;
+    use std::time::Instant;
+
+    /// A request seen by the stand-in server.
+    #[derive(Debug)]
+    struct Seen {
+        method: String,
+        url: String,
+        cookie: Option<String>,
+        body: String,
+    }
+
+    /// Starts a server answering the given responses in order, then
+    /// stopping, and returns its base URL and what it was sent.
+    fn stand_in(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<Seen>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, response) in responses {
+                let mut request = server.recv().unwrap();
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|header| header.field.equiv("Cookie"))
+                    .map(|header| header.value.to_string());
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                sender
+                    .send(Seen {
+                        method: request.method().to_string(),
+                        url: request.url().to_string(),
+                        cookie,
+                        body,
+                    })
+                    .unwrap();
+                let response = tiny_http::Response::from_string(response).with_status_code(status);
+                request.respond(response).unwrap();
+            }
+        });
+
+        (base_url, receiver)
+    }
+
+    fn client(base_url: String, dir: &Path) -> Client {
+        let remote = Remote {
+            session: Some(String::from("cafe")),
+            base_url,
+            year: 2022,
+            min_interval: 0,
+        };
+        let throttle = Throttle::new(dir.join("last-request"), remote.min_interval());
+        Client::new(&remote, throttle)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let dir = scratch_dir("downloads-input");
+        let (base_url, seen) = stand_in(vec![(200, "1000\n2000\n"), (200, "3000\n")]);
+        let client = client(base_url, &dir);
+
+        // Like the placeholder of a new day
+        let path = dir.join("input.txt");
+        fs::write(&path, "").unwrap();
+        assert_eq!(
+            fetch_input(&client, 1, &path, false),
+            Ok(Fetched::Downloaded)
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+        let request = seen.recv().unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.url, "/2022/day/1/input");
+        assert_eq!(request.cookie.as_deref(), Some("session=cafe"));
+
+        assert_eq!(fetch_input(&client, 1, &path, false), Ok(Fetched::Cached));
+        assert!(seen.try_recv().is_err());
+
+        assert_eq!(
+            fetch_input(&client, 1, &path, true),
+            Ok(Fetched::Downloaded)
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3000\n");
+    }
+
+    #[test]
+    fn reports_errors() {
+        let dir = scratch_dir("reports-errors");
+        let (base_url, _seen) = stand_in(vec![(404, "Not found"), (400, "Please log in")]);
+        let client = client(base_url, &dir);
+
+        assert_eq!(
+            client.input(25),
+            Err(String::from("the input of day 25 is not available yet"))
+        );
+        assert!(client.input(1).unwrap_err().contains("session token"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let dir = scratch_dir("submits-answers");
+        let (base_url, seen) = stand_in(vec![
+            (
+                200,
+                "<article><p>That's the right answer! You are one gold star closer.</p></article>",
+            ),
+            (
+                200,
+                "<article><p>That's not the right answer; your answer is too low.</p></article>",
+            ),
+        ]);
+        let client = client(base_url, &dir);
+
+        assert_eq!(client.answer(5, Part::One, "CMZ"), Ok(Verdict::Correct));
+        let request = seen.recv().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.url, "/2022/day/5/answer");
+        assert_eq!(request.cookie.as_deref(), Some("session=cafe"));
+        assert_eq!(request.body, "level=1&answer=CMZ");
+
+        assert_eq!(
+            client.answer(1, Part::Two, "a b"),
+            Ok(Verdict::Rejected(Rejection::TooLow))
+        );
+        assert_eq!(seen.recv().unwrap().body, "level=2&answer=a+b");
+    }
+
+    #[test]
+    fn verdicts() {
+        assert_eq!(
+            Verdict::from_page(
+                "That's not the right answer; your answer is too high. \
+                 Please wait one minute before trying again."
+            ),
+            Verdict::Rejected(Rejection::TooHigh)
+        );
+        assert_eq!(
+            Verdict::from_page(
+                "That's not the right answer. If you're stuck, make sure you're using the full input data"
+            ),
+            Verdict::Rejected(Rejection::Wrong)
+        );
+        assert_eq!(
+            Verdict::from_page(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 24s left to wait."
+            ),
+            Verdict::Wait(Duration::from_secs(84))
+        );
+        assert_eq!(
+            Verdict::from_page("You gave an answer too recently. You have 39s left to wait."),
+            Verdict::Wait(Duration::from_secs(39))
+        );
+        assert_eq!(
+            Verdict::from_page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(Verdict::from_page("<html></html>"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn needs_a_session() {
+        let dir = scratch_dir("needs-a-session");
+        let throttle = Throttle::new(dir.join("last-request"), Duration::ZERO);
+        let client = Client::new(&Remote::default(), throttle);
+        assert!(client.input(1).unwrap_err().contains("no session token"));
+    }
+
+    #[test]
+    fn throttles_requests() {
+        let dir = scratch_dir("throttles-requests");
+        let interval = Duration::from_millis(200);
+
+        let start = Instant::now();
+        Throttle::new(dir.join("last-request"), interval)
+            .wait()
+            .unwrap();
+        assert!(start.elapsed() < interval / 2);
+
+        // Another throttle with the same file, like in a later run
+        let start = Instant::now();
+        Throttle::new(dir.join("last-request"), interval)
+            .wait()
+            .unwrap();
+        assert!(start.elapsed() >= interval / 2);
+    }
+}
@@ -0,0 +1,82 @@
+use common::Run;
+use serde_json::{json, Map, Value};
+
+/// A day's run as a JSON document, for tools rather than people.
+///
+/// Answers are strings, like in the answers registry, and are null when
+/// the input has none. Only the parts that were run are in it, with the
+/// options of the puzzle they were solved with.
+pub fn document(day: u8, run: &Run, options: &Value) -> Value {
+    let mut answers = Map::new();
+    let mut timing = Map::new();
+    timing.insert(String::from("parse_ns"), json!(run.parse.as_nanos() as u64));
+
+    for ((part, answer), duration) in run.answers.iter().zip(&run.parts) {
+        answers.insert(format!("part{part}"), json!(answer));
+        timing.insert(format!("part{part}_ns"), json!(duration.as_nanos() as u64));
+    }
+
+    json!({
+        "day": day,
+        "answers": answers,
+        "timing": timing,
+        "details": run.details,
+        "options": options,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Part;
+    use std::time::Duration;
+
+    #[test]
+    fn document() {
+        let run = Run {
+            answers: vec![(Part::One, Some(String::from("CMZ"))), (Part::Two, None)],
+            parse: Duration::from_nanos(1500),
+            parts: vec![Duration::from_nanos(20), Duration::from_nanos(30)],
+            details: None,
+        };
+
+        assert_eq!(
+            super::document(5, &run, &json!({})),
+            json!({
+                "day": 5,
+                "answers": { "part1": "CMZ", "part2": null },
+                "timing": { "parse_ns": 1500, "part1_ns": 20, "part2_ns": 30 },
+                "details": null,
+                "options": {},
+            })
+        );
+    }
+
+    #[test]
+    fn details() {
+        let day = crate::days::find(7).unwrap();
+        let input = std::fs::read_to_string(day.data_file("test-input")).unwrap();
+        let options = (day.options)(&Value::Null).unwrap();
+        let run = (day.run)(&mut input.as_bytes(), &[Part::Two], &options).unwrap();
+        let document = super::document(7, &run, &options);
+
+        assert_eq!(document["answers"], json!({ "part2": "24933642" }));
+        assert_eq!(document["details"]["deleted_dir"]["path"], "/d");
+        assert!(document["timing"]["part1_ns"].is_null());
+        assert_eq!(document["options"]["total_size"], 70_000_000);
+    }
+
+    #[test]
+    fn snapshots() {
+        for day in crate::testing::solved_days() {
+            let input = std::fs::read_to_string(day.data_file("test-input")).unwrap();
+            let options = (day.options)(&Value::Null).unwrap();
+            let run = (day.run)(&mut input.as_bytes(), &Part::BOTH, &options).unwrap();
+            let mut document = super::document(day.number, &run, &options);
+            // Only the timings change from one run to the next
+            document["timing"] = Value::Null;
+
+            common::assert_json_snapshot!(format!("day{:02}", day.number), document);
+        }
+    }
+}
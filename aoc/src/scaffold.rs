@@ -0,0 +1,238 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use toml_edit::{DocumentMut, InlineTable, Item, Value};
+
+// Files of a new day, by their path in the day's folder
+const TEMPLATES: [(&str, &str); 5] = [
+    ("Cargo.toml", include_str!("../templates/day/Cargo.toml")),
+    ("src/lib.rs", include_str!("../templates/day/lib.rs")),
+    ("src/main.rs", include_str!("../templates/day/main.rs")),
+    (
+        "src/generate.rs",
+        include_str!("../templates/day/generate.rs"),
+    ),
+    (
+        "data/answers.toml",
+        include_str!("../templates/day/answers.toml"),
+    ),
+];
+
+// Inputs to paste the puzzle's example and the real input into
+const FIXTURES: [&str; 2] = ["data/test-input.txt", "data/input.txt"];
+
+fn render(template: &str, number: u8) -> String {
+    template
+        .replace("{{NN}}", &format!("{number:02}"))
+        .replace("{{N}}", &number.to_string())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("could not read {}: {err}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("could not create {}: {err}", dir.display()))?;
+    }
+    fs::write(path, contents).map_err(|err| format!("could not write {}: {err}", path.display()))
+}
+
+/// Creates the crate of day `number` in the workspace at `root`, and
+/// registers it with the workspace and the runner.
+///
+/// Returns the files that were created or changed.
+pub fn new_day(root: &Path, number: u8) -> Result<Vec<PathBuf>, String> {
+    let name = format!("day{number:02}");
+    let dir = root.join(&name);
+    // Its `data/` may be there already, with a downloaded input
+    if dir.join("Cargo.toml").exists() || dir.join("src").exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let mut changed = Vec::new();
+
+    for (file, template) in TEMPLATES {
+        let path = dir.join(file);
+        write(&path, &render(template, number))?;
+        changed.push(path);
+    }
+    for file in FIXTURES {
+        let path = dir.join(file);
+        if !path.exists() {
+            write(&path, "")?;
+            changed.push(path);
+        }
+    }
+
+    let manifest = root.join("Cargo.toml");
+    write(&manifest, &add_member(&read(&manifest)?, &name)?)?;
+    changed.push(manifest);
+
+    let manifest = root.join("aoc").join("Cargo.toml");
+    write(&manifest, &add_dependency(&read(&manifest)?, &name)?)?;
+    changed.push(manifest);
+
+    let days = root.join("aoc").join("src").join("days.rs");
+    write(&days, &add_day(&read(&days)?, number)?)?;
+    changed.push(days);
+
+    Ok(changed)
+}
+
+fn add_member(manifest: &str, name: &str) -> Result<String, String> {
+    let mut document: DocumentMut = manifest
+        .parse()
+        .map_err(|err| format!("invalid workspace manifest: {err}"))?;
+    let members = document["workspace"]["members"]
+        .as_array_mut()
+        .ok_or("the workspace manifest has no `members` list")?;
+
+    let index = members
+        .iter()
+        .position(|member| member.as_str().is_some_and(|member| member > name))
+        .unwrap_or(members.len());
+    // Laid out like the others, one per line
+    let decor = members.get(0).map(|member| member.decor().clone());
+    members.insert(index, name);
+    if let (Some(decor), Some(member)) = (decor, members.get_mut(index)) {
+        *member.decor_mut() = decor;
+    }
+
+    Ok(document.to_string())
+}
+
+fn add_dependency(manifest: &str, name: &str) -> Result<String, String> {
+    let mut document: DocumentMut = manifest
+        .parse()
+        .map_err(|err| format!("invalid runner manifest: {err}"))?;
+    let dependencies = document["dependencies"]
+        .as_table_mut()
+        .ok_or("the runner manifest has no dependencies")?;
+
+    let mut dependency = InlineTable::new();
+    dependency.insert("path", Value::from(format!("../{name}")));
+    dependencies.insert(name, Item::Value(Value::InlineTable(dependency)));
+    dependencies.sort_values();
+
+    Ok(document.to_string())
+}
+
+// The days are listed in order in `DAYS`, whose length has to follow
+fn add_day(days: &str, number: u8) -> Result<String, String> {
+    let entry = |number: u8| format!("    day!({number}, day{number:02}::Day{number:02}),");
+    let not_found = || String::from("could not find the `DAYS` list in days.rs");
+
+    let mut lines: Vec<String> = days.lines().map(String::from).collect();
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const DAYS: [Day; "))
+        .ok_or_else(not_found)?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line == "];")
+            .ok_or_else(not_found)?;
+
+    let registered: Vec<u8> = lines[start + 1..end]
+        .iter()
+        .filter_map(|line| {
+            let (number, _) = line.trim().strip_prefix("day!(")?.split_once(',')?;
+            number.parse().ok()
+        })
+        .collect();
+    if registered.contains(&number) {
+        return Err(format!("day {number} is already registered in days.rs"));
+    }
+
+    let index = registered
+        .iter()
+        .position(|&registered| registered > number)
+        .unwrap_or(registered.len());
+    lines.insert(start + 1 + index, entry(number));
+    lines[start] = format!("pub const DAYS: [Day; {}] = [", registered.len() + 1);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use crate::testing::scratch_dir;
+
+    #[test]
+    fn renders_templates() {
+        let lib = render(TEMPLATES[1].1, 7);
+        assert!(lib.starts_with("//! Day 7.\n"));
+        assert!(lib.contains("pub struct Day07;"));
+        assert!(!lib.contains("{{"));
+    }
+
+    #[test]
+    fn adds_members() {
+        let manifest =
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day03\",\n]\n";
+        assert_eq!(
+            add_member(manifest, "day02").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day02\",\n    \"day03\",\n]\n"
+        );
+        assert_eq!(
+            add_member(manifest, "day11").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day03\",\n    \"day11\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn adds_dependencies() {
+        let manifest = "[dependencies]\ncommon = { path = \"../common\" }\nday01 = { path = \"../day01\" }\nserde = \"1.0\"\n";
+        assert_eq!(
+            add_dependency(manifest, "day02").unwrap(),
+            "[dependencies]\ncommon = { path = \"../common\" }\nday01 = { path = \"../day01\" }\nday02 = { path = \"../day02\" }\nserde = \"1.0\"\n"
+        );
+    }
+
+    #[test]
+    fn adds_days() {
+        let days = "pub const DAYS: [Day; 2] = [\n    day!(1, day01::Day01),\n    day!(3, day03::Day03),\n];\n";
+        assert_eq!(
+            add_day(days, 2).unwrap(),
+            "pub const DAYS: [Day; 3] = [\n    day!(1, day01::Day01),\n    day!(2, day02::Day02),\n    day!(3, day03::Day03),\n];\n"
+        );
+        assert!(add_day(days, 3).is_err());
+    }
+
+    #[test]
+    fn creates_days() {
+        let root = scratch_dir("creates-days");
+        for file in ["Cargo.toml", "aoc/Cargo.toml", "aoc/src/days.rs"] {
+            write(&root.join(file), &read(&days::root().join(file)).unwrap()).unwrap();
+        }
+        let number = (1..=25)
+            .find(|&number| days::find(number).is_none())
+            .unwrap();
+        let name = format!("day{number:02}");
+        // Downloaded before the day was created
+        let day = root.join(&name);
+        write(&day.join("data/input.txt"), "input\n").unwrap();
+
+        let changed = new_day(&root, number).unwrap();
+        assert_eq!(changed.len(), TEMPLATES.len() + 1 + 3);
+
+        assert!(day.join("src/lib.rs").is_file());
+        assert_eq!(read(&day.join("data/test-input.txt")).unwrap(), "");
+        assert_eq!(read(&day.join("data/input.txt")).unwrap(), "input\n");
+        assert!(read(&root.join("Cargo.toml"))
+            .unwrap()
+            .contains(&format!("    \"{name}\",\n")));
+        assert!(read(&root.join("aoc/Cargo.toml"))
+            .unwrap()
+            .contains(&format!("{name} = {{ path = \"../{name}\" }}\n")));
+        assert!(read(&root.join("aoc/src/days.rs"))
+            .unwrap()
+            .contains(&format!("    day!({number}, {name}::Day{number:02}),\n")));
+
+        assert!(new_day(&root, number).is_err());
+    }
+}
@@ -0,0 +1,23 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use crate::answers::Registry;
+use crate::days::{Day, DAYS};
+
+/// A folder of its own for a test, emptied first.
+pub fn scratch_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-{}-{name}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// The days with known answers, leaving out the ones just created from the
+/// templates, which solve their inputs but have no answers to check yet.
+pub fn solved_days() -> impl Iterator<Item = &'static Day> {
+    DAYS.iter().filter(|day| {
+        Registry::load(&day.answers_file()).is_ok_and(|registry| registry.inputs().next().is_some())
+    })
+}
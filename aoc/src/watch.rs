@@ -0,0 +1,304 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use serde_json::Value;
+
+use crate::check;
+use crate::days;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// When and how big every file under some folders was, to tell when
+/// one of them changed without relying on the platform's notifications.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    files: BTreeMap<PathBuf, (SystemTime, u64)>,
+}
+
+impl Snapshot {
+    pub fn take(dirs: &[PathBuf]) -> Snapshot {
+        let mut snapshot = Snapshot::default();
+        for dir in dirs {
+            snapshot.add(dir);
+        }
+        snapshot
+    }
+
+    // Files that vanish while being looked at are simply left out
+    fn add(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                self.add(&entry.path());
+            } else if let Ok(modified) = metadata.modified() {
+                self.files.insert(entry.path(), (modified, metadata.len()));
+            }
+        }
+    }
+
+    /// Files added, removed or modified since `before`.
+    pub fn changes(&self, before: &Snapshot) -> Vec<PathBuf> {
+        let mut changes: Vec<PathBuf> = self
+            .files
+            .iter()
+            .filter(|(path, stamp)| before.files.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changes.extend(
+            before
+                .files
+                .keys()
+                .filter(|path| !self.files.contains_key(*path))
+                .cloned(),
+        );
+        changes
+    }
+}
+
+/// Answers of each part, or why there are none.
+pub type Answers = BTreeMap<String, Result<Vec<(String, Option<String>)>, String>>;
+
+fn cargo() -> Command {
+    // Set when the runner itself was started by cargo
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let mut command = Command::new(cargo);
+    command.current_dir(days::root());
+    command
+}
+
+/// The answers in the runner's `--format json` document.
+pub fn parse_answers(document: &str) -> Result<Vec<(String, Option<String>)>, String> {
+    let document: Value = serde_json::from_str(document)
+        .map_err(|err| format!("could not read the runner's output: {err}"))?;
+    let answers = document["answers"]
+        .as_object()
+        .ok_or("the runner's output has no answers")?;
+
+    Ok(answers
+        .iter()
+        .map(|(part, answer)| (part.clone(), answer.as_str().map(String::from)))
+        .collect())
+}
+
+// Solved by the runner built from the current sources, not by this one
+fn solve(day: u8, input: &Path) -> Result<Vec<(String, Option<String>)>, String> {
+    let output = cargo()
+        .args(["run", "--quiet", "--package", "aoc", "--"])
+        .arg("--day")
+        .arg(day.to_string())
+        .arg("--input")
+        .arg(input)
+        .args(["--format", "json"])
+        .output()
+        .map_err(|err| format!("could not run cargo: {err}"))?;
+
+    if output.status.success() {
+        parse_answers(&String::from_utf8_lossy(&output.stdout))
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+// Every text file in `data/`, like `test-input.txt` and `input.txt`
+fn inputs(day: u8) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(days::dir(day).join("data")) else {
+        return Vec::new();
+    };
+    let mut inputs: Vec<(String, PathBuf)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .filter_map(|path| Some((path.file_stem()?.to_str()?.to_string(), path)))
+        .collect();
+    inputs.sort();
+    inputs
+}
+
+fn run_tests(day: u8) -> Result<(), String> {
+    let output = cargo()
+        .args(["test", "--quiet", "--package", &format!("day{day:02}")])
+        .output()
+        .map_err(|err| format!("could not run cargo: {err}"))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("{}\n{}", stdout.trim(), stderr.trim())
+            .trim()
+            .to_string())
+    }
+}
+
+/// How the answers of each input changed since the previous run, a line
+/// per part, or the error that kept an input from being solved.
+pub fn diff(previous: &Answers, current: &Answers) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for (input, answers) in current {
+        let answers = match answers {
+            Ok(answers) => answers,
+            Err(err) => {
+                lines.push(format!("{input}: {err}"));
+                continue;
+            }
+        };
+        let before = match previous.get(input) {
+            Some(Ok(before)) => Some(before),
+            _ => None,
+        };
+
+        for (part, answer) in answers {
+            let old = before.and_then(|before| {
+                let (_, answer) = before.iter().find(|(other, _)| other == part)?;
+                Some(answer)
+            });
+            let describe = |answer: &Option<String>| {
+                answer
+                    .as_deref()
+                    .map_or(String::from("no answer"), check::summary)
+            };
+
+            let change = match old {
+                None => describe(answer),
+                Some(old) if old == answer => format!("{} (unchanged)", describe(answer)),
+                Some(old) => format!("{} -> {}", describe(old), describe(answer)),
+            };
+            lines.push(format!("{input} {part}: {change}"));
+        }
+    }
+
+    lines
+}
+
+/// Runs the tests and every input of `day` each time its sources or data
+/// change, until interrupted.
+pub fn watch(day: u8) -> Result<(), String> {
+    let dir = days::dir(day);
+    if !dir.is_dir() {
+        return Err(format!("{} does not exist", dir.display()));
+    }
+    let dirs = [dir.join("src"), dir.join("data")];
+
+    let mut previous = Answers::new();
+    let mut snapshot = Snapshot::take(&dirs);
+
+    loop {
+        println!("Day {day}: running the tests");
+        match run_tests(day) {
+            Ok(()) => println!("tests passed"),
+            Err(output) => println!("tests failed:\n{output}"),
+        }
+
+        let current: Answers = inputs(day)
+            .into_iter()
+            .map(|(input, path)| (input, solve(day, &path)))
+            .collect();
+        for line in diff(&previous, &current) {
+            println!("{line}");
+        }
+        previous = current;
+
+        println!("\nWatching {} for changes...", dir.display());
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if !Snapshot::take(&dirs).changes(&snapshot).is_empty() {
+                break;
+            }
+        }
+
+        // Editors often write a file in a few steps, so let it settle
+        thread::sleep(POLL_INTERVAL);
+        let current = Snapshot::take(&dirs);
+        println!();
+        for path in current.changes(&snapshot) {
+            let path = path.strip_prefix(days::root()).unwrap_or(&path);
+            println!("changed: {}", path.display());
+        }
+        snapshot = current;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::scratch_dir;
+
+    #[test]
+    fn snapshots() {
+        let dir = scratch_dir("snapshots");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "fn main() {}").unwrap();
+        fs::write(dir.join("input.txt"), "1").unwrap();
+
+        let dirs = [dir.clone()];
+        let before = Snapshot::take(&dirs);
+        assert!(Snapshot::take(&dirs).changes(&before).is_empty());
+
+        fs::write(dir.join("src/lib.rs"), "fn main() { }").unwrap();
+        fs::write(dir.join("src/cpu.rs"), "").unwrap();
+        fs::remove_file(dir.join("input.txt")).unwrap();
+        assert_eq!(
+            Snapshot::take(&dirs).changes(&before),
+            [
+                dir.join("src/cpu.rs"),
+                dir.join("src/lib.rs"),
+                dir.join("input.txt")
+            ]
+        );
+    }
+
+    #[test]
+    fn answers_from_json() {
+        assert_eq!(
+            parse_answers(r#"{"day": 5, "answers": {"part1": "CMZ", "part2": null}}"#),
+            Ok(vec![
+                (String::from("part1"), Some(String::from("CMZ"))),
+                (String::from("part2"), None)
+            ])
+        );
+        assert!(parse_answers("error").is_err());
+    }
+
+    #[test]
+    fn diffs() {
+        let answers = |part1: &str, part2: Option<&str>| {
+            Ok(vec![
+                (String::from("part1"), Some(String::from(part1))),
+                (String::from("part2"), part2.map(String::from)),
+            ])
+        };
+        let previous = Answers::from([
+            (String::from("input"), answers("24000", None)),
+            (String::from("test-input"), answers("1", Some("2"))),
+        ]);
+        let current = Answers::from([
+            (String::from("input"), answers("24000", Some("45000"))),
+            (String::from("other"), answers("3", Some("#.\n.#\n"))),
+            (String::from("test-input"), Err(String::from("error: oops"))),
+        ]);
+
+        assert_eq!(
+            diff(&previous, &current),
+            [
+                "input part1: 24000 (unchanged)",
+                "input part2: no answer -> 45000",
+                "other part1: 3",
+                "other part2: #. (+1 lines)",
+                "test-input: error: oops",
+            ]
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use common::{Generator, Rng};
+
+use crate::Day{{NN}};
+
+impl Generator for Day{{NN}} {
+    const SIZE: usize = 1000;
+
+    // `size` is the number of lines, each a number
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| format!("{}\n", rng.range(1..=999)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{NoOptions, Solution};
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = common::generate::<Day{{NN}}>(seed as usize * 10, seed);
+            let lines = Day{{NN}}::parse(&input).unwrap();
+            assert!(Day{{NN}}::part1(&lines, &NoOptions {}).is_some());
+            assert!(Day{{NN}}::part2(&lines, &NoOptions {}).is_some());
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(
+            common::generate::<Day{{NN}}>(50, 1),
+            common::generate::<Day{{NN}}>(50, 1)
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Day {{N}}.
+//!
+//! Until the puzzle is solved, part 1 counts the input's lines and part 2
+//! the ones that are not blank, so that the day already solves any input.
+//!
+//! ```
+//! use common::{NoOptions, Solution};
+//! use day{{NN}}::Day{{NN}};
+//!
+//! let lines = Day{{NN}}::parse("1\n\n2\n").unwrap();
+//! assert_eq!(Day{{NN}}::part1(&lines, &NoOptions {}), Some(3));
+//! assert_eq!(Day{{NN}}::part2(&lines, &NoOptions {}), Some(2));
+//! ```
+
+use common::{NoOptions, ParseError, Solution};
+use std::io::BufRead;
+
+mod generate;
+
+pub struct Day{{NN}};
+
+impl Solution for Day{{NN}} {
+    type Input = Vec<String>;
+    type Options = NoOptions;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn read(input: impl BufRead) -> Result<Vec<String>, ParseError> {
+        common::lines(input).map(|line| Ok(line?.1)).collect()
+    }
+
+    fn part1(lines: &Vec<String>, _options: &NoOptions) -> Option<u64> {
+        Some(lines.len() as u64)
+    }
+
+    fn part2(lines: &Vec<String>, _options: &NoOptions) -> Option<u64> {
+        Some(lines.iter().filter(|line| !line.trim().is_empty()).count() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn test_input() -> Vec<String> {
+        let input = fs::read_to_string("data/test-input.txt").unwrap();
+        Day{{NN}}::parse(&input).unwrap()
+    }
+
+    // To change to the answers of the example in the puzzle, once pasted
+    // into `data/test-input.txt`
+    #[test]
+    fn part1() {
+        assert_eq!(Day{{NN}}::part1(&test_input(), &NoOptions {}), Some(0));
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(Day{{NN}}::part2(&test_input(), &NoOptions {}), Some(0));
+    }
+}
@@ -0,0 +1,12 @@
+use std::fs::File;
+use std::io::BufReader;
+
+use common::ParseError;
+use day{{NN}}::Day{{NN}};
+
+fn main() -> Result<(), ParseError> {
+    let input = File::open("data/input.txt").expect("Input file should exist at data/input.txt");
+    common::print_answers::<Day{{NN}}>(BufReader::new(input))?;
+
+    Ok(())
+}
@@ -0,0 +1,278 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A number a `Point` can be made of.
+///
+/// Unsigned coordinates stop at 0, so moving a point can fail,
+/// which is what grids indexed by `usize` want at their edges.
+pub trait Coordinate: Copy + Ord + Hash + Debug + Add<Output = Self> {
+    /// `self + delta`, or None when it does not fit in the type.
+    fn checked_offset(self, delta: isize) -> Option<Self>;
+
+    /// How far apart both coordinates are, always positive.
+    fn distance(self, other: Self) -> Self;
+}
+
+macro_rules! unsigned_coordinate {
+    ($($coordinate:ty => $signed:ty),*) => {$(
+        impl Coordinate for $coordinate {
+            fn checked_offset(self, delta: isize) -> Option<Self> {
+                self.checked_add_signed(<$signed>::try_from(delta).ok()?)
+            }
+
+            fn distance(self, other: Self) -> Self {
+                self.abs_diff(other)
+            }
+        }
+    )*};
+}
+
+macro_rules! signed_coordinate {
+    ($($coordinate:ty),*) => {$(
+        impl Coordinate for $coordinate {
+            fn checked_offset(self, delta: isize) -> Option<Self> {
+                self.checked_add(<$coordinate>::try_from(delta).ok()?)
+            }
+
+            fn distance(self, other: Self) -> Self {
+                (self - other).abs()
+            }
+        }
+    )*};
+}
+
+unsigned_coordinate!(usize => isize, u32 => i32, u64 => i64);
+signed_coordinate!(isize, i32, i64);
+
+/// One of the four directions of a grid.
+///
+/// Like in the puzzles' drawings, y grows going down,
+/// so `Up` is towards the first row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise, starting from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// How x and y change when taking a step in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.turn_right().turn_right()
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.opposite().turn_right()
+    }
+}
+
+/// One of the eight directions of a grid, diagonals included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Clockwise, starting from `Up`.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// How x and y change when taking a step in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction8::Up => (0, -1),
+            Direction8::UpRight => (1, -1),
+            Direction8::Right => (1, 0),
+            Direction8::DownRight => (1, 1),
+            Direction8::Down => (0, 1),
+            Direction8::DownLeft => (-1, 1),
+            Direction8::Left => (-1, 0),
+            Direction8::UpLeft => (-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Direction8 {
+        match direction {
+            Direction::Up => Direction8::Up,
+            Direction::Right => Direction8::Right,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+        }
+    }
+}
+
+/// A point of a 2D plane or grid.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coordinate> Point<T> {
+    pub fn new(x: T, y: T) -> Point<T> {
+        Point { x, y }
+    }
+
+    /// The point `dx` and `dy` away, if its coordinates fit in `T`.
+    pub fn offset(self, dx: isize, dy: isize) -> Option<Point<T>> {
+        Some(Point {
+            x: self.x.checked_offset(dx)?,
+            y: self.y.checked_offset(dy)?,
+        })
+    }
+
+    /// The next point in `direction`, which can be a `Direction`
+    /// or a `Direction8`.
+    pub fn step(self, direction: impl Into<Direction8>) -> Option<Point<T>> {
+        let (dx, dy) = direction.into().delta();
+        self.offset(dx, dy)
+    }
+
+    /// The up to 4 points next to this one, diagonals excluded.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point<T>> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(direction))
+    }
+
+    /// The up to 8 points around this one, diagonals included.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point<T>> {
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(direction))
+    }
+
+    /// Distance when only moving horizontally or vertically.
+    pub fn manhattan(&self, other: &Point<T>) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    /// Distance when also moving diagonally, like a king in chess.
+    pub fn chebyshev(&self, other: &Point<T>) -> T {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+
+    /// One step closer to `other`, diagonally if they are not in line.
+    pub fn towards(self, other: &Point<T>) -> Point<T> {
+        let delta = |from: T, to: T| match from.cmp(&to) {
+            Ordering::Less => 1,
+            Ordering::Equal => 0,
+            Ordering::Greater => -1,
+        };
+        self.offset(delta(self.x, other.x), delta(self.y, other.y))
+            .expect("A point between two others fits in their type")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps() {
+        let point = Point::new(0usize, 1);
+        assert_eq!(point.step(Direction::Up), Some(Point::new(0, 0)));
+        assert_eq!(point.step(Direction::Left), None);
+        assert_eq!(point.step(Direction8::DownRight), Some(Point::new(1, 2)));
+
+        let point = Point::new(0i32, 0);
+        assert_eq!(point.step(Direction::Left), Some(Point::new(-1, 0)));
+        assert_eq!(Point::new(i32::MAX, 0).step(Direction::Right), None);
+    }
+
+    #[test]
+    fn directions() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.opposite().opposite(), direction);
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            let (dx, dy) = direction.delta();
+            assert_eq!(direction.opposite().delta(), (-dx, -dy));
+            assert_eq!(Direction8::from(direction).delta(), (dx, dy));
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    }
+
+    #[test]
+    fn neighbours() {
+        assert_eq!(Point::new(5i64, 5).neighbours4().count(), 4);
+        assert_eq!(Point::new(5i64, 5).neighbours8().count(), 8);
+        assert_eq!(
+            Point::new(0usize, 0).neighbours4().collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(Point::new(0usize, 3).neighbours8().count(), 5);
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(1i32, -2);
+        let b = Point::new(-3, 4);
+        assert_eq!(a.manhattan(&b), 10);
+        assert_eq!(a.chebyshev(&b), 6);
+        assert_eq!(a.manhattan(&a), 0);
+
+        let a = Point::new(1usize, 7);
+        let b = Point::new(4usize, 2);
+        assert_eq!(a.manhattan(&b), 8);
+        assert_eq!(b.chebyshev(&a), 5);
+    }
+
+    #[test]
+    fn towards() {
+        let target = Point::new(3i32, -1);
+        assert_eq!(Point::new(0, 0).towards(&target), Point::new(1, -1));
+        assert_eq!(Point::new(3, 5).towards(&target), Point::new(3, 4));
+        assert_eq!(target.towards(&target), target);
+        assert_eq!(
+            Point::new(0usize, 0).towards(&Point::new(0, 2)),
+            Point::new(0, 1)
+        );
+    }
+}
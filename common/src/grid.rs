@@ -0,0 +1,216 @@
+use std::iter;
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{Direction8, Point};
+
+/// A rectangle of cells, stored row after row.
+///
+/// Points are columns (`x`) and rows (`y`), both starting at 0
+/// from the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of the given size with every cell set to `cell`.
+    pub fn new(width: usize, height: usize, cell: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![cell; width * height],
+        }
+    }
+
+    /// A grid of these rows, or None when they are not all as wide.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point<usize>) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn get(&self, point: Point<usize>) -> Option<&T> {
+        if self.contains(point) {
+            self.cells.get(point.y * self.width + point.x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, point: Point<usize>) -> Option<&mut T> {
+        if self.contains(point) {
+            self.cells.get_mut(point.y * self.width + point.x)
+        } else {
+            None
+        }
+    }
+
+    /// Every point of the grid, row after row.
+    pub fn points(&self) -> impl Iterator<Item = Point<usize>> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Every cell with its point, row after row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.height {
+            Some(&self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // A grid without columns still has its rows, only empty
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Cells of column `x`, from top to bottom. Empty outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let rows = if x < self.width { self.height } else { 0 };
+        (0..rows).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    /// Cells seen going from `from` in `direction` until the edge of the
+    /// grid, with their points. The cell at `from` is not part of it.
+    pub fn ray(
+        &self,
+        from: Point<usize>,
+        direction: impl Into<Direction8>,
+    ) -> impl Iterator<Item = (Point<usize>, &T)> {
+        let direction = direction.into();
+        iter::successors(from.step(direction), move |point| point.step(direction))
+            .map_while(|point| Some((point, self.get(point)?)))
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<usize>) -> &T {
+        self.get(point).unwrap_or_else(|| {
+            panic!(
+                "Point {point:?} is outside of the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<usize>) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("Point {point:?} is outside of the {width}x{height} grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Direction;
+
+    fn grid() -> Grid<u8> {
+        Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap()
+    }
+
+    #[test]
+    fn from_rows() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height(), grid.len()), (3, 2, 6));
+        assert_eq!(grid[Point::new(2, 1)], 6);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+        assert!(Grid::<u8>::from_rows(Vec::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(grid.column(3).count(), 0);
+    }
+
+    #[test]
+    fn points() {
+        let grid = grid();
+        let points: Vec<Point<usize>> = grid.points().collect();
+        assert_eq!(
+            points[..4],
+            [
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(2, 0),
+                Point::new(0, 1)
+            ]
+        );
+        assert_eq!(grid.iter().nth(4), Some((Point::new(1, 1), &5)));
+    }
+
+    #[test]
+    fn rays() {
+        let grid = grid();
+        let ray = |from, direction: Direction8| -> Vec<u8> {
+            grid.ray(from, direction).map(|(_, &cell)| cell).collect()
+        };
+        assert_eq!(ray(Point::new(0, 0), Direction::Right.into()), [2, 3]);
+        assert_eq!(ray(Point::new(2, 1), Direction::Left.into()), [5, 4]);
+        assert_eq!(ray(Point::new(0, 0), Direction8::DownRight), [5]);
+        assert!(ray(Point::new(0, 0), Direction::Up.into()).is_empty());
+    }
+
+    #[test]
+    fn index_mut() {
+        let mut grid = Grid::new(2, 2, '.');
+        grid[Point::new(1, 0)] = '#';
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [&['.', '#'][..], &['.', '.']]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_outside() {
+        let _ = grid()[Point::new(0, 2)];
+    }
+}
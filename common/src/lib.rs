@@ -0,0 +1,250 @@
+use std::fmt::{self, Display};
+use std::hint::black_box;
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+
+pub mod geometry;
+mod grid;
+mod options;
+mod parse;
+mod random;
+pub mod snapshot;
+pub mod trace;
+pub mod visual;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub use geometry::{Direction, Direction8, Point};
+pub use grid::Grid;
+pub use options::{options, NoOptions};
+pub use parse::{fields, groups, lines, parse_groups, parse_line, ParseError};
+pub use random::Rng;
+pub use visual::Animation;
+// For `event!` and `assert_json_snapshot!` in the days, which do not all
+// depend on it
+#[doc(hidden)]
+pub use serde_json;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// A day's puzzle, split into parsing the input and solving each part.
+///
+/// Parsing happens once, and both parts work on the parsed input,
+/// so each day can be driven the same way regardless of its models.
+pub trait Solution {
+    type Input;
+    /// Knobs of the puzzle, like a threshold or a size, which default to
+    /// the puzzle's own values. [`NoOptions`] for a puzzle without any.
+    type Options: Default + Serialize + DeserializeOwned;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    /// Parses the input while reading it, so that it never has to be
+    /// in memory as a whole, whether it comes from a file, a pipe or stdin.
+    fn read(input: impl BufRead) -> Result<Self::Input, ParseError>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::read(input.as_bytes())
+    }
+
+    // None means the input has no answer for this part
+    fn part1(input: &Self::Input, options: &Self::Options) -> Option<Self::Answer1>;
+    fn part2(input: &Self::Input, options: &Self::Options) -> Option<Self::Answer2>;
+
+    /// Intermediate results worth showing next to the answers,
+    /// like what an answer was computed from.
+    fn details(_input: &Self::Input, _options: &Self::Options) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Frames of how `part` is solved, for the puzzles worth watching.
+    ///
+    /// At most `max_frames` of them, or all of them for 0: the ones a
+    /// [`visual::Sampling`] keeps, so that long solutions only draw those.
+    fn animation(
+        _input: &Self::Input,
+        _part: Part,
+        _options: &Self::Options,
+        _max_frames: usize,
+    ) -> Option<Animation> {
+        None
+    }
+}
+
+/// A day that can make up random inputs for its puzzle, which are
+/// always valid and have an answer for both parts.
+pub trait Generator {
+    /// Roughly the size of a real input, in the unit `generate` uses.
+    const SIZE: usize;
+
+    /// `size` is how many of the input's items (elves, rounds, lines...)
+    /// to make, which each day documents on its implementation.
+    fn generate(size: usize, rng: &mut Rng) -> String;
+}
+
+/// Generates an input of `size` items, the same one for the same `seed`.
+pub fn generate<G: Generator>(size: usize, seed: u64) -> String {
+    G::generate(size, &mut Rng::new(seed))
+}
+
+/// Each requested part with its answer, if the input has one.
+pub type Answers = Vec<(Part, Option<String>)>;
+
+/// Parses `input` once and solves the requested parts, in order.
+///
+/// Answers are rendered to strings so that every day can be
+/// handled the same way, whatever its answer types are.
+pub fn solve<S: Solution>(
+    input: impl BufRead,
+    parts: &[Part],
+    options: &S::Options,
+) -> Result<Answers, ParseError> {
+    let input = S::read(input)?;
+
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            let answer = match part {
+                Part::One => S::part1(&input, options).map(|answer| answer.to_string()),
+                Part::Two => S::part2(&input, options).map(|answer| answer.to_string()),
+            };
+            (part, answer)
+        })
+        .collect();
+
+    Ok(answers)
+}
+
+/// The answers of a single run, with how long each step took.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub answers: Answers,
+    pub parse: Duration,
+    // One for each answer, in the same order
+    pub parts: Vec<Duration>,
+    pub details: Option<serde_json::Value>,
+}
+
+/// Like `solve`, but also times each step and gathers the day's details.
+///
+/// Parsing is timed along with reading the input.
+pub fn run<S: Solution>(
+    input: impl BufRead,
+    parts: &[Part],
+    options: &S::Options,
+) -> Result<Run, ParseError> {
+    let start = Instant::now();
+    let parsed = S::read(input)?;
+    let parse = start.elapsed();
+
+    let mut answers = Vec::new();
+    let mut timings = Vec::new();
+    for &part in parts {
+        let start = Instant::now();
+        let answer = match part {
+            Part::One => S::part1(&parsed, options).map(|answer| answer.to_string()),
+            Part::Two => S::part2(&parsed, options).map(|answer| answer.to_string()),
+        };
+        timings.push(start.elapsed());
+        answers.push((part, answer));
+    }
+
+    Ok(Run {
+        answers,
+        parse,
+        parts: timings,
+        details: S::details(&parsed, options),
+    })
+}
+
+/// Parses `input` and draws how `part` is solved in at most `max_frames`
+/// frames, None when the day cannot draw it.
+pub fn animate<S: Solution>(
+    input: impl BufRead,
+    part: Part,
+    options: &S::Options,
+    max_frames: usize,
+) -> Result<Option<Animation>, ParseError> {
+    let animation = S::animation(&S::read(input)?, part, options, max_frames);
+    // For days drawing every frame anyway
+    Ok(animation.map(|animation| animation.sample(max_frames)))
+}
+
+/// How long each step of a solution took, once per iteration.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Vec<Duration>,
+    pub part1: Vec<Duration>,
+    pub part2: Vec<Duration>,
+}
+
+/// Parses and solves `input` over and over, timing each step on its own.
+///
+/// The input is read from memory, to only time parsing it.
+pub fn bench<S: Solution>(
+    input: &str,
+    iterations: usize,
+    options: &S::Options,
+) -> Result<Timings, ParseError> {
+    let mut timings = Timings::default();
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = S::parse(black_box(input))?;
+        timings.parse.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(&parsed, options));
+        timings.part1.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part2(&parsed, options));
+        timings.part2.push(start.elapsed());
+    }
+
+    Ok(timings)
+}
+
+/// Parses `input` and prints both answers, one part after the other,
+/// with the puzzle's own options.
+pub fn print_answers<S: Solution>(input: impl BufRead) -> Result<(), ParseError> {
+    for (part, answer) in solve::<S>(input, &Part::BOTH, &S::Options::default())? {
+        print_answer(part, answer.as_deref());
+    }
+    Ok(())
+}
+
+pub fn print_answer(part: Part, answer: Option<&str>) {
+    match answer {
+        Some(answer) => {
+            if answer.contains('\n') {
+                println!("Part {part}:\n{answer}");
+            } else {
+                println!("Part {part}: {answer}");
+            }
+        }
+        None => println!("Part {part}: no answer"),
+    }
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::Solution;
+
+/// Options of a puzzle without any, which refuses every override.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NoOptions {}
+
+/// The options of `S`, taken from `overrides` where it sets them
+/// and left to the puzzle's own values elsewhere.
+///
+/// `overrides` is an object of options by name, or null for none.
+/// Options `S` does not have, or of the wrong type, are an error.
+pub fn options<S: Solution>(overrides: &Value) -> Result<S::Options, String> {
+    match overrides {
+        Value::Null => Ok(S::Options::default()),
+        overrides => serde_json::from_value(overrides.clone()).map_err(|err| err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseError;
+    use serde_json::json;
+    use std::io::BufRead;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct Knobs {
+        size: usize,
+        name: String,
+    }
+
+    struct Puzzle;
+
+    impl Solution for Puzzle {
+        type Input = ();
+        type Options = Knobs;
+        type Answer1 = u8;
+        type Answer2 = u8;
+
+        fn read(_input: impl BufRead) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part1(_input: &(), _options: &Knobs) -> Option<u8> {
+            None
+        }
+
+        fn part2(_input: &(), _options: &Knobs) -> Option<u8> {
+            None
+        }
+    }
+
+    #[test]
+    fn overrides() {
+        assert_eq!(options::<Puzzle>(&Value::Null), Ok(Knobs::default()));
+        assert_eq!(
+            options::<Puzzle>(&json!({ "size": 4 })),
+            Ok(Knobs {
+                size: 4,
+                name: String::new()
+            })
+        );
+        assert!(options::<Puzzle>(&json!({ "sise": 4 })).is_err());
+        assert!(options::<Puzzle>(&json!({ "size": "four" })).is_err());
+    }
+
+    #[test]
+    fn no_options() {
+        let options: NoOptions = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(options, NoOptions {});
+        assert!(serde_json::from_value::<NoOptions>(json!({ "size": 4 })).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// Where and why an input could not be parsed.
+///
+/// Lines and columns both start at 1, and columns count characters,
+/// not bytes, so they match what an editor shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Moves an error found while parsing a piece of the input to where
+    /// that piece starts in the whole input.
+    ///
+    /// Parsers of a single line report positions within that line,
+    /// and their callers know at which line (and column) it really is.
+    pub fn relocate(self, line: usize, column: usize) -> ParseError {
+        let column = if self.line == 1 {
+            column + self.column - 1
+        } else {
+            self.column
+        };
+
+        ParseError {
+            line: line + self.line - 1,
+            column,
+            message: self.message,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// The lines of `input`, numbered from 1 and without their `\n` or `\r\n`,
+/// like `str::lines` but as they are read.
+///
+/// A line that cannot be read, because reading failed or because it is
+/// not UTF-8, is an error at that line.
+pub fn lines(input: impl BufRead) -> impl Iterator<Item = Result<(usize, String), ParseError>> {
+    input.lines().enumerate().map(|(i, line)| {
+        line.map(|line| (i + 1, line))
+            .map_err(|err| ParseError::new(i + 1, 1, format!("could not read the line: {err}")))
+    })
+}
+
+/// Splits a line on whitespace, like `str::split_whitespace`,
+/// but also gives the column where each field starts.
+pub fn fields(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut fields = Vec::new();
+    let mut start = None;
+
+    for (column, (index, chr)) in line.char_indices().enumerate() {
+        match (chr.is_whitespace(), start) {
+            (false, None) => start = Some((column + 1, index)),
+            (true, Some((field_column, field_index))) => {
+                fields.push((field_column, &line[field_index..index]));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+
+    if let Some((field_column, field_index)) = start {
+        fields.push((field_column, &line[field_index..]));
+    }
+
+    fields.into_iter()
+}
+
+struct Groups<I> {
+    lines: I,
+}
+
+impl<I> Iterator for Groups<I>
+where
+    I: Iterator<Item = Result<(usize, String), ParseError>>,
+{
+    type Item = Result<Vec<(usize, String)>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut group = Vec::new();
+
+        loop {
+            match self.lines.next() {
+                Some(Ok((_, line))) if line.trim().is_empty() => {
+                    if !group.is_empty() {
+                        return Some(Ok(group));
+                    }
+                }
+                Some(Ok(line)) => group.push(line),
+                Some(Err(err)) => return Some(Err(err)),
+                // The last group needs no blank line after it
+                None => return (!group.is_empty()).then_some(Ok(group)),
+            }
+        }
+    }
+}
+
+/// The lines of each group of `input`, numbered from 1 like with
+/// [`lines`].
+///
+/// Groups are separated by one blank line or more, lines of whitespace
+/// included, which can also come before the first group or after the last.
+///
+/// ```
+/// let input = "a\r\nb\n\n \n\nc".as_bytes();
+/// let groups: Vec<_> = common::groups(input).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(groups[0], [(1, String::from("a")), (2, String::from("b"))]);
+/// assert_eq!(groups[1], [(6, String::from("c"))]);
+/// ```
+pub fn groups(
+    input: impl BufRead,
+) -> impl Iterator<Item = Result<Vec<(usize, String)>, ParseError>> {
+    Groups {
+        lines: lines(input),
+    }
+}
+
+/// Like [`groups`], with every line parsed as a `T`, the only thing on the
+/// line besides whitespace. Errors say that `what` was expected.
+pub fn parse_groups<T: FromStr>(
+    input: impl BufRead,
+    what: &'static str,
+) -> impl Iterator<Item = Result<Vec<T>, ParseError>> {
+    groups(input).map(move |group| {
+        group?
+            .into_iter()
+            .map(|(number, line)| parse_line(&line, what).map_err(|err| err.relocate(number, 1)))
+            .collect()
+    })
+}
+
+/// A line parsed as a `T`, the only thing on it besides whitespace, with
+/// errors at line 1 like for any single line.
+pub fn parse_line<T: FromStr>(line: &str, what: &str) -> Result<T, ParseError> {
+    let value = line.trim();
+    value.parse().map_err(|_| {
+        let column = fields(line).next().map_or(1, |(column, _)| column);
+        ParseError::new(1, column, format!("expected {what}, found `{value}`"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relocate() {
+        let error = ParseError::new(1, 3, "bad");
+        assert_eq!(error.relocate(10, 5), ParseError::new(10, 7, "bad"));
+
+        let error = ParseError::new(2, 3, "bad");
+        assert_eq!(error.relocate(10, 5), ParseError::new(11, 3, "bad"));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            ParseError::new(4, 2, "expected a number").to_string(),
+            "line 4, column 2: expected a number"
+        );
+    }
+
+    #[test]
+    fn numbered_lines() {
+        let read: Vec<_> = lines("a\r\nb\n\nc".as_bytes()).collect();
+        assert_eq!(
+            read,
+            [
+                Ok((1, String::from("a"))),
+                Ok((2, String::from("b"))),
+                Ok((3, String::new())),
+                Ok((4, String::from("c")))
+            ]
+        );
+
+        let read: Vec<_> = lines(&b"a\n\xff\n"[..]).collect();
+        assert_eq!(read[1].as_ref().unwrap_err().line(), 2);
+    }
+
+    #[test]
+    fn split_fields() {
+        assert_eq!(
+            fields("addx -5").collect::<Vec<_>>(),
+            vec![(1, "addx"), (6, "-5")]
+        );
+        assert_eq!(
+            fields("  move 1  from 2 ").collect::<Vec<_>>(),
+            vec![(3, "move"), (8, "1"), (11, "from"), (16, "2")]
+        );
+        assert_eq!(fields("é b").collect::<Vec<_>>(), vec![(1, "é"), (3, "b")]);
+        assert_eq!(fields("").count(), 0);
+    }
+
+    fn numbers(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+        parse_groups(input.as_bytes(), "a number").collect()
+    }
+
+    #[test]
+    fn separators() {
+        assert_eq!(numbers("1\n2\n\n3\n"), Ok(vec![vec![1, 2], vec![3]]));
+        assert_eq!(numbers("1\n2\n\n3"), Ok(vec![vec![1, 2], vec![3]]));
+        assert_eq!(numbers("\n\n1\n\n\n\n2\n\n\n"), Ok(vec![vec![1], vec![2]]));
+        assert_eq!(
+            numbers("1\r\n2\r\n\r\n3\r\n"),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+        assert_eq!(numbers("1\n \t\n  2  \n"), Ok(vec![vec![1], vec![2]]));
+        assert_eq!(numbers(""), Ok(Vec::new()));
+        assert_eq!(numbers("\n \n"), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            numbers("1\n\n  3k00\n"),
+            Err(ParseError::new(3, 3, "expected a number, found `3k00`"))
+        );
+        assert_eq!(
+            numbers("1\n\n10 00\n"),
+            Err(ParseError::new(3, 1, "expected a number, found `10 00`"))
+        );
+        assert_eq!(
+            numbers("1\n-2\n"),
+            Err(ParseError::new(2, 1, "expected a number, found `-2`"))
+        );
+    }
+}
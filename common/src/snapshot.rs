@@ -0,0 +1,225 @@
+//! Golden files: outputs, like renders and reports, compared with the ones
+//! saved under the crate's `data/snapshots/`.
+//!
+//! A test fails with a diff when its output changed. Running the tests
+//! with `AOC_UPDATE_SNAPSHOTS=1` saves every output instead, once they are
+//! the expected ones.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Set to anything but `0` to save the outputs instead of comparing them.
+pub const UPDATE_VAR: &str = "AOC_UPDATE_SNAPSHOTS";
+
+// Unchanged lines shown around each change
+const CONTEXT: usize = 2;
+
+/// Whether the outputs are saved instead of compared, from [`UPDATE_VAR`].
+pub fn updating() -> bool {
+    env::var_os(UPDATE_VAR).is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Compares `actual` with the snapshot `file_name` in `dir`, or saves it
+/// there when `update` is set. The error tells how they differ.
+pub fn check(dir: &Path, file_name: &str, actual: &str, update: bool) -> Result<(), String> {
+    let valid = |c: char| c.is_ascii_alphanumeric() || "-_.".contains(c);
+    if file_name.is_empty() || file_name.starts_with('.') || !file_name.chars().all(valid) {
+        return Err(format!("invalid snapshot name `{file_name}`"));
+    }
+    let path = dir.join(file_name);
+
+    if update {
+        fs::create_dir_all(dir)
+            .and_then(|()| fs::write(&path, actual))
+            .map_err(|err| format!("could not save {}: {err}", path.display()))?;
+        return Ok(());
+    }
+
+    let Ok(saved) = fs::read_to_string(&path) else {
+        return Err(format!(
+            "no snapshot at {}, run the test with {UPDATE_VAR}=1 \
+             to save this output:\n{actual}",
+            path.display()
+        ));
+    };
+    // As checked out on Windows
+    let saved = saved.replace("\r\n", "\n");
+    if saved == actual {
+        return Ok(());
+    }
+
+    let diff = match diff(&saved, actual) {
+        diff if diff.is_empty() => String::from("  (only the newline at the end differs)\n"),
+        diff => diff,
+    };
+    Err(format!(
+        "snapshot {} does not match (- saved, + actual):\n{diff}\
+         run the test with {UPDATE_VAR}=1 to accept the actual output",
+        path.display()
+    ))
+}
+
+/// The lines of `old` and `new`, marked `-` when only in `old`, `+` when
+/// only in `new`, with a few unchanged ones around. Empty when they have
+/// the same lines.
+pub fn diff(old: &str, new: &str) -> String {
+    let (old, new): (Vec<&str>, Vec<&str>) = (old.lines().collect(), new.lines().collect());
+
+    // The longest common subsequence of what is left once the lines both
+    // start and end with are set aside
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (middle_old, middle_new) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+    if middle_old.is_empty() && middle_new.is_empty() {
+        return String::new();
+    }
+
+    let (n, m) = (middle_old.len(), middle_new.len());
+    let mut common = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            common[i][j] = if middle_old[i] == middle_new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines: Vec<(char, &str)> = old[..prefix].iter().map(|line| (' ', *line)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && middle_old[i] == middle_new[j] {
+            lines.push((' ', middle_old[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if j == m || (i < n && common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(('-', middle_old[i]));
+            i += 1;
+        } else {
+            lines.push(('+', middle_new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[old.len() - suffix..].iter().map(|line| (' ', *line)));
+
+    let changed: Vec<usize> = (0..lines.len())
+        .filter(|&index| lines[index].0 != ' ')
+        .collect();
+    let shown = |index: usize| {
+        changed
+            .iter()
+            .any(|&change| change.abs_diff(index) <= CONTEXT)
+    };
+
+    let mut diff = String::new();
+    let mut skipping = false;
+    for (index, (mark, line)) in lines.iter().enumerate() {
+        if shown(index) {
+            diff.push_str(&format!("{mark} {line}\n"));
+            skipping = false;
+        } else if !skipping {
+            diff.push_str("  ...\n");
+            skipping = true;
+        }
+    }
+    diff
+}
+
+/// Compares a string with the snapshot `$name.txt` in the calling crate's
+/// `data/snapshots/`, and panics with a diff when it changed.
+///
+/// ```no_run
+/// let screen = "##..\n..##\n";
+/// common::assert_snapshot!("screen", screen);
+/// ```
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $actual:expr) => {
+        if let Err(err) = $crate::snapshot::check(
+            ::std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/data/snapshots")),
+            &format!("{}.txt", $name),
+            ::std::convert::AsRef::<str>::as_ref(&$actual),
+            $crate::snapshot::updating(),
+        ) {
+            panic!("{err}");
+        }
+    };
+}
+
+/// Like [`assert_snapshot!`], for anything serializable, saved as pretty
+/// printed JSON in `$name.json`.
+#[macro_export]
+macro_rules! assert_json_snapshot {
+    ($name:expr, $actual:expr) => {
+        if let Err(err) = $crate::snapshot::check(
+            ::std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/data/snapshots")),
+            &format!("{}.json", $name),
+            &($crate::serde_json::to_string_pretty(&$actual).expect("Snapshots are serializable")
+                + "\n"),
+            $crate::snapshot::updating(),
+        ) {
+            panic!("{err}");
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("common-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn diffs() {
+        assert_eq!(diff("a\nb\n", "a\nb"), "");
+        assert_eq!(diff("a\nb\nc\n", "a\nx\nc\n"), "  a\n- b\n+ x\n  c\n");
+        assert_eq!(diff("a\n", "a\nb\n"), "  a\n+ b\n");
+        assert_eq!(diff("", "a\n"), "+ a\n");
+
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let new = "0\n1\n2\n3\n4\n5\n6\n7\n9\n";
+        assert_eq!(diff(old, new), "+ 0\n  1\n  2\n  ...\n  6\n  7\n- 8\n  9\n");
+    }
+
+    #[test]
+    fn save_and_compare() {
+        let dir = scratch_dir("snapshots");
+
+        let missing = check(&dir, "screen.txt", "##..\n", false).unwrap_err();
+        assert!(missing.contains(UPDATE_VAR));
+        assert!(missing.ends_with("##..\n"));
+
+        check(&dir, "screen.txt", "##..\n", true).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("screen.txt")).unwrap(),
+            "##..\n"
+        );
+        check(&dir, "screen.txt", "##..\n", false).unwrap();
+
+        let changed = check(&dir, "screen.txt", "#...\n", false).unwrap_err();
+        assert!(changed.contains("- ##..\n+ #...\n"));
+        let newline = check(&dir, "screen.txt", "##..", false).unwrap_err();
+        assert!(newline.contains("only the newline at the end differs"));
+
+        fs::write(dir.join("screen.txt"), "##..\r\n").unwrap();
+        check(&dir, "screen.txt", "##..\n", false).unwrap();
+
+        assert!(check(&dir, "../screen.txt", "", true).is_err());
+        assert!(check(&dir, "", "", true).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
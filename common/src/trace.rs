@@ -0,0 +1,287 @@
+//! Structured events about what the solvers do, written as JSON lines.
+//!
+//! Solvers send events with [`event!`](crate::event), which cost a single
+//! atomic load while no tracer is installed. Each event has a level and
+//! comes from a target, the module path it was sent from, and a
+//! [`Filter`] keeps the ones wanted.
+
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, PoisonError, RwLock};
+use std::time::Instant;
+
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+// None is `off`, which no event is at
+fn parse_level(string: &str) -> Result<Option<Level>, String> {
+    match string.trim() {
+        "off" => Ok(None),
+        "error" => Ok(Some(Level::Error)),
+        "warn" => Ok(Some(Level::Warn)),
+        "info" => Ok(Some(Level::Info)),
+        "debug" => Ok(Some(Level::Debug)),
+        "trace" => Ok(Some(Level::Trace)),
+        other => Err(format!(
+            "unknown level `{other}`, expected off, error, warn, info, debug or trace"
+        )),
+    }
+}
+
+/// Which events to keep, as a list of comma separated directives: a level,
+/// like `debug`, for every target, or `target=level` for a target and the
+/// modules inside it, like `day09=trace` or `day07::fs=off`.
+///
+/// The directive of the longest matching target wins, and targets
+/// without one keep nothing unless a level for every target is given.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    default: Option<Level>,
+    targets: Vec<(String, Option<Level>)>,
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Filter, String> {
+        let mut filter = Filter::default();
+
+        for directive in string.split(',').map(str::trim) {
+            match directive.split_once('=') {
+                _ if directive.is_empty() => (),
+                Some((target, level)) if !target.trim().is_empty() => {
+                    let target = target.trim().to_string();
+                    filter.targets.push((target, parse_level(level)?));
+                }
+                Some(_) => return Err(format!("missing target in `{directive}`")),
+                None => filter.default = parse_level(directive)?,
+            }
+        }
+
+        Ok(filter)
+    }
+}
+
+impl Filter {
+    /// The most detailed level kept for `target`, if any.
+    pub fn level(&self, target: &str) -> Option<Level> {
+        let matches = |prefix: &str| {
+            target == prefix
+                || target
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with("::"))
+        };
+
+        match self
+            .targets
+            .iter()
+            .filter(|(prefix, _)| matches(prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+        {
+            Some((_, level)) => *level,
+            None => self.default,
+        }
+    }
+
+    pub fn enabled(&self, level: Level, target: &str) -> bool {
+        self.level(target).is_some_and(|kept| level <= kept)
+    }
+
+    // Above it, events are dropped without looking at their target
+    fn max_level(&self) -> u8 {
+        self.targets
+            .iter()
+            .map(|(_, level)| *level)
+            .chain([self.default])
+            .flatten()
+            .max()
+            .map_or(0, |level| level as u8)
+    }
+}
+
+struct Tracer {
+    filter: Filter,
+    out: Mutex<Box<dyn Write + Send>>,
+    start: Instant,
+}
+
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+static TRACER: RwLock<Option<Tracer>> = RwLock::new(None);
+
+/// Starts writing the events `filter` keeps to `out`, a line each,
+/// instead of to the tracer installed before, if any.
+pub fn install(filter: Filter, out: impl Write + Send + 'static) {
+    let mut tracer = TRACER.write().unwrap_or_else(PoisonError::into_inner);
+    MAX_LEVEL.store(filter.max_level(), Ordering::Relaxed);
+    *tracer = Some(Tracer {
+        filter,
+        out: Mutex::new(Box::new(out)),
+        start: Instant::now(),
+    });
+}
+
+/// Stops tracing, after writing out the events still buffered.
+pub fn finish() {
+    MAX_LEVEL.store(0, Ordering::Relaxed);
+    let tracer = TRACER
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    if let Some(tracer) = tracer {
+        let mut out = tracer
+            .out
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Tracing never gets in the way of solving
+        let _ = out.flush();
+    }
+}
+
+/// Whether an event of `level` from `target` would be written.
+pub fn enabled(level: Level, target: &str) -> bool {
+    if level as u8 > MAX_LEVEL.load(Ordering::Relaxed) {
+        return false;
+    }
+    let tracer = TRACER.read().unwrap_or_else(PoisonError::into_inner);
+    tracer
+        .as_ref()
+        .is_some_and(|tracer| tracer.filter.enabled(level, target))
+}
+
+/// Writes an event, unless the filter drops it. Use [`event!`](crate::event)
+/// instead, which only builds the fields of the events that are kept.
+pub fn emit(level: Level, target: &str, event: &str, fields: Value) {
+    let tracer = TRACER.read().unwrap_or_else(PoisonError::into_inner);
+    let Some(tracer) = tracer.as_ref() else {
+        return;
+    };
+    if !tracer.filter.enabled(level, target) {
+        return;
+    }
+
+    let line = json!({
+        "elapsed_us": tracer.start.elapsed().as_micros() as u64,
+        "level": level.name(),
+        "target": target,
+        "event": event,
+        "fields": fields,
+    });
+    let mut out = tracer.out.lock().unwrap_or_else(PoisonError::into_inner);
+    let _ = writeln!(out, "{line}");
+}
+
+/// Sends an event named `$event` at `$level`, from the calling module,
+/// with the fields of a JSON object written like with `serde_json::json!`.
+///
+/// ```
+/// use common::trace::Level;
+///
+/// let (from, to) = (1, 3);
+/// common::event!(Level::Debug, "move", { "from": from, "to": to });
+/// ```
+#[macro_export]
+macro_rules! event {
+    ($level:expr, $event:expr, { $($fields:tt)* }) => {
+        if $crate::trace::enabled($level, module_path!()) {
+            $crate::trace::emit(
+                $level,
+                module_path!(),
+                $event,
+                $crate::serde_json::json!({ $($fields)* }),
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(bytes)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn filters() {
+        let filter: Filter = "info, day07=debug, day09::rope=trace, day09::rope::knots=off"
+            .parse()
+            .unwrap();
+        assert_eq!(filter.level("day05"), Some(Level::Info));
+        assert_eq!(filter.level("day07::fs"), Some(Level::Debug));
+        assert_eq!(filter.level("day070"), Some(Level::Info));
+        assert_eq!(filter.level("day09::rope"), Some(Level::Trace));
+        assert_eq!(filter.level("day09::rope::knots"), None);
+        assert!(filter.enabled(Level::Warn, "day05"));
+        assert!(!filter.enabled(Level::Debug, "day05"));
+        assert_eq!(filter.max_level(), Level::Trace as u8);
+
+        let filter: Filter = "day10=trace".parse().unwrap();
+        assert_eq!(filter.level("day01"), None);
+        assert_eq!(filter.level("day10::cpu"), Some(Level::Trace));
+
+        assert_eq!("".parse::<Filter>().unwrap().max_level(), 0);
+        assert!("loud".parse::<Filter>().is_err());
+        assert!("=debug".parse::<Filter>().is_err());
+        assert!("day01=loud".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn json_lines() {
+        let buffer = Buffer::default();
+        install("common=debug".parse().unwrap(), buffer.clone());
+        crate::event!(Level::Debug, "kept", { "value": 1 });
+        crate::event!(Level::Trace, "dropped", { "value": 2 });
+        finish();
+        crate::event!(Level::Debug, "after", { "value": 3 });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["level"], "debug");
+        assert_eq!(lines[0]["target"], "common::trace::tests");
+        assert_eq!(lines[0]["event"], "kept");
+        assert_eq!(lines[0]["fields"], json!({ "value": 1 }));
+        assert!(lines[0]["elapsed_us"].is_u64());
+    }
+}
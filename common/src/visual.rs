@@ -0,0 +1,527 @@
+//! Pictures of how a puzzle is solved: frames of a grid of cells, exported
+//! as text, SVG, animated SVG or animated PNG.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{Grid, Point};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    // The website's colors
+    pub const BACKGROUND: Color = Color(0x0f, 0x0f, 0x23);
+    pub const FOREGROUND: Color = Color(0xcc, 0xcc, 0xcc);
+    pub const DIM: Color = Color(0x33, 0x33, 0x40);
+    pub const GREEN: Color = Color(0x00, 0x99, 0x00);
+    pub const GOLD: Color = Color(0xff, 0xff, 0x66);
+    pub const RED: Color = Color(0xff, 0x44, 0x44);
+
+    /// The color `t` of the way from this one to `other`, `t` going from
+    /// 0 to 1.
+    pub fn towards(self, other: Color, t: f64) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Color(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+
+    fn is_light(self) -> bool {
+        299 * self.0 as u32 + 587 * self.1 as u32 + 114 * self.2 as u32 > 128_000
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// A glyph, shown as is in text and written over the cell's color in SVG
+/// when it is a letter or a digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub color: Color,
+}
+
+impl Cell {
+    pub const EMPTY: Cell = Cell {
+        glyph: ' ',
+        color: Color::BACKGROUND,
+    };
+
+    pub fn new(glyph: char, color: Color) -> Cell {
+        Cell { glyph, color }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub cells: Grid<Cell>,
+    // What happens in the frame, like the move it shows
+    pub caption: String,
+}
+
+impl Frame {
+    /// A frame of empty cells.
+    pub fn new(width: usize, height: usize) -> Frame {
+        Frame {
+            cells: Grid::new(width, height, Cell::EMPTY),
+            caption: String::new(),
+        }
+    }
+
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Frame {
+        self.caption = caption.into();
+        self
+    }
+
+    /// Sets the cell at column `x` of row `y`, unless it is outside.
+    pub fn set(&mut self, x: usize, y: usize, cell: Cell) {
+        if let Some(old) = self.cells.get_mut(Point::new(x, y)) {
+            *old = cell;
+        }
+    }
+
+    /// The glyphs, a line per row, without the spaces ending them.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for row in self.cells.rows() {
+            let line: String = row.iter().map(|cell| cell.glyph).collect();
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// Frames shown one after the other, each for `delay`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Animation {
+    pub frames: Vec<Frame>,
+    pub delay: Duration,
+}
+
+impl Animation {
+    pub fn new(frames: Vec<Frame>, delay: Duration) -> Animation {
+        Animation { frames, delay }
+    }
+
+    /// At most `max` frames, evenly spread over the animation, the first
+    /// and the last one included, each shown for longer so that it lasts
+    /// about as long.
+    pub fn sample(self, max: usize) -> Animation {
+        let sampling = Sampling::new(self.frames.len(), max);
+        if sampling.kept() == self.frames.len() {
+            return self;
+        }
+        Animation {
+            delay: sampling.delay(self.delay),
+            frames: self
+                .frames
+                .into_iter()
+                .enumerate()
+                .filter(|(index, _)| sampling.keeps(*index))
+                .map(|(_, frame)| frame)
+                .collect(),
+        }
+    }
+
+    // Of the largest frame, for frames that are not all as big
+    fn size(&self) -> (usize, usize) {
+        let width = self.frames.iter().map(|frame| frame.cells.width());
+        let height = self.frames.iter().map(|frame| frame.cells.height());
+        (width.max().unwrap_or(0), height.max().unwrap_or(0))
+    }
+}
+
+/// Which of `count` frames an animation of at most `max` frames keeps, like
+/// [`Animation::sample`], so that a day only draws those. A `max` of 0
+/// keeps them all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sampling {
+    count: usize,
+    max: usize,
+}
+
+impl Sampling {
+    pub fn new(count: usize, max: usize) -> Sampling {
+        Sampling { count, max }
+    }
+
+    /// Whether the frame at `index` is kept.
+    pub fn keeps(&self, index: usize) -> bool {
+        let (count, max) = (self.count, self.max);
+        if index >= count {
+            return false;
+        }
+        match max {
+            _ if count <= max || max == 0 => true,
+            1 => index == count - 1,
+            // Kept frames are at `kept * (count - 1) / (max - 1)`, never
+            // next to each other
+            _ => {
+                let kept = (index * (max - 1)).div_ceil(count - 1);
+                kept * (count - 1) / (max - 1) == index
+            }
+        }
+    }
+
+    /// How many frames are kept.
+    pub fn kept(&self) -> usize {
+        match self.max {
+            0 => self.count,
+            max => self.count.min(max),
+        }
+    }
+
+    /// How long each kept frame is shown for the animation to last about
+    /// as long as with every frame shown for `delay`.
+    pub fn delay(&self, delay: Duration) -> Duration {
+        match self.kept() {
+            0 => delay,
+            kept => delay * self.count as u32 / kept as u32,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Every frame's glyphs, one frame after the other.
+    Ascii,
+    /// The last frame only.
+    Svg,
+    AnimatedSvg,
+    Apng,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Format, String> {
+        match string {
+            "ascii" => Ok(Format::Ascii),
+            "svg" => Ok(Format::Svg),
+            "animated-svg" => Ok(Format::AnimatedSvg),
+            "apng" => Ok(Format::Apng),
+            _ => Err(format!(
+                "unknown format `{string}`, expected ascii, svg, animated-svg or apng"
+            )),
+        }
+    }
+}
+
+impl Format {
+    /// The format of a file with this extension: text for `.txt`, an
+    /// animated SVG for `.svg` and an animated PNG for `.png` or `.apng`.
+    pub fn from_extension(extension: &str) -> Option<Format> {
+        match extension.to_ascii_lowercase().as_str() {
+            "txt" => Some(Format::Ascii),
+            "svg" => Some(Format::AnimatedSvg),
+            "png" | "apng" => Some(Format::Apng),
+            _ => None,
+        }
+    }
+}
+
+/// Writes `animation` to `out` in `format`.
+pub fn export(animation: &Animation, format: Format, mut out: impl Write) -> io::Result<()> {
+    match format {
+        Format::Ascii => out.write_all(ascii(animation).as_bytes()),
+        Format::Svg => {
+            let last = animation.frames.len().saturating_sub(1);
+            let still = Animation::new(animation.frames[last..].to_vec(), animation.delay);
+            out.write_all(svg(&still).as_bytes())
+        }
+        Format::AnimatedSvg => out.write_all(svg(animation).as_bytes()),
+        Format::Apng => apng(animation, out),
+    }
+}
+
+pub fn ascii(animation: &Animation) -> String {
+    let count = animation.frames.len();
+    let mut text = String::new();
+
+    for (index, frame) in animation.frames.iter().enumerate() {
+        if index > 0 {
+            text.push('\n');
+        }
+        match (count, frame.caption.as_str()) {
+            (1, "") => (),
+            (1, caption) => text.push_str(&format!("{caption}\n")),
+            (_, "") => text.push_str(&format!("frame {}/{count}\n", index + 1)),
+            (_, caption) => text.push_str(&format!("frame {}/{count}: {caption}\n", index + 1)),
+        }
+        text.push_str(&frame.text());
+    }
+    text
+}
+
+// In SVG units, which are pixels unless the picture is scaled
+const SVG_CELL: usize = 10;
+const SVG_CAPTION: usize = 16;
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Every frame over the one before, each only visible for the animation's
+/// delay, over and over. A single frame is a still picture.
+pub fn svg(animation: &Animation) -> String {
+    let (width, height) = animation.size();
+    let count = animation.frames.len();
+    let top = if animation
+        .frames
+        .iter()
+        .any(|frame| !frame.caption.is_empty())
+    {
+        SVG_CAPTION
+    } else {
+        0
+    };
+    let (picture_width, picture_height) = (width * SVG_CELL, height * SVG_CELL + top);
+    let duration = animation.delay.as_millis() as usize * count;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{picture_width}" height="{picture_height}" viewBox="0 0 {picture_width} {picture_height}" font-family="monospace" font-size="{SVG_CELL}" text-anchor="middle">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        Color::BACKGROUND.hex()
+    );
+
+    for (index, frame) in animation.frames.iter().enumerate() {
+        if count == 1 {
+            svg.push_str("<g>\n");
+        } else {
+            // Hidden but during its own share of the animation
+            let (start, end) = (
+                index as f64 / count as f64,
+                (index + 1) as f64 / count as f64,
+            );
+            let (values, times) = match index {
+                0 => (String::from("visible;hidden"), format!("0;{end}")),
+                _ if index == count - 1 => (String::from("hidden;visible"), format!("0;{start}")),
+                _ => (
+                    String::from("hidden;visible;hidden"),
+                    format!("0;{start};{end}"),
+                ),
+            };
+            let _ = writeln!(
+                svg,
+                r#"<g visibility="hidden"><animate attributeName="visibility" calcMode="discrete" values="{values}" keyTimes="{times}" dur="{duration}ms" repeatCount="indefinite"/>"#
+            );
+        }
+
+        if !frame.caption.is_empty() {
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" fill="{}">{}</text>"#,
+                picture_width / 2,
+                top - 4,
+                Color::FOREGROUND.hex(),
+                escape(&frame.caption)
+            );
+        }
+        for (point, cell) in frame.cells.iter() {
+            let (x, y) = (point.x * SVG_CELL, point.y * SVG_CELL + top);
+            if cell.color != Color::BACKGROUND {
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{x}" y="{y}" width="{SVG_CELL}" height="{SVG_CELL}" fill="{}"/>"#,
+                    cell.color.hex()
+                );
+            }
+            if cell.glyph.is_alphanumeric() {
+                let ink = if cell.color.is_light() {
+                    Color::BACKGROUND
+                } else {
+                    Color::FOREGROUND
+                };
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" fill="{}">{}</text>"#,
+                    x + SVG_CELL / 2,
+                    y + SVG_CELL - 2,
+                    ink.hex(),
+                    cell.glyph
+                );
+            }
+        }
+        svg.push_str("</g>\n");
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// Pixels of a cell, smaller for big grids to keep the picture about as
+// wide as a screen
+fn png_cell(width: usize, height: usize) -> usize {
+    (800 / width.max(height).max(1)).clamp(1, 8)
+}
+
+/// An animated PNG of the frames' colors, which loops forever. Glyphs and
+/// captions are left out.
+pub fn apng(animation: &Animation, out: impl Write) -> io::Result<()> {
+    let (width, height) = animation.size();
+    let cell = png_cell(width, height);
+    let (picture_width, picture_height) = ((width * cell).max(1), (height * cell).max(1));
+    let too_big = |_| io::Error::new(io::ErrorKind::InvalidInput, "the frames are too big");
+
+    let mut encoder = png::Encoder::new(
+        out,
+        u32::try_from(picture_width).map_err(too_big)?,
+        u32::try_from(picture_height).map_err(too_big)?,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let frames = animation.frames.len().max(1);
+    encoder.set_animated(frames as u32, 0)?;
+    let delay = animation.delay.as_millis().min(u16::MAX as u128) as u16;
+    encoder.set_frame_delay(delay, 1000)?;
+
+    let mut writer = encoder.write_header()?;
+    let blank = Frame::new(0, 0);
+    for frame in animation
+        .frames
+        .iter()
+        .chain(animation.frames.is_empty().then_some(&blank))
+    {
+        let mut pixels = Vec::with_capacity(picture_width * picture_height * 3);
+        for y in 0..picture_height {
+            for x in 0..picture_width {
+                let color = frame
+                    .cells
+                    .get(Point::new(x / cell, y / cell))
+                    .map_or(Color::BACKGROUND, |cell| cell.color);
+                pixels.extend([color.0, color.1, color.2]);
+            }
+        }
+        writer.write_image_data(&pixels)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn animation() -> Animation {
+        let frames = (0..3)
+            .map(|index| {
+                let mut frame = Frame::new(3, 2).with_caption(format!("step {index}"));
+                frame.set(index, 0, Cell::new('#', Color::GOLD));
+                frame.set(index, 1, Cell::new('A', Color::GREEN));
+                frame
+            })
+            .collect();
+        Animation::new(frames, Duration::from_millis(100))
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            ascii(&animation()),
+            "frame 1/3: step 0\n#\nA\n\nframe 2/3: step 1\n #\n A\n\nframe 3/3: step 2\n  #\n  A\n"
+        );
+
+        let mut frame = Frame::new(2, 1);
+        frame.set(0, 0, Cell::new('#', Color::GOLD));
+        frame.set(5, 5, Cell::new('#', Color::GOLD));
+        let still = Animation::new(vec![frame], Duration::ZERO);
+        assert_eq!(ascii(&still), "#\n");
+    }
+
+    #[test]
+    fn sample() {
+        let sampled = animation().sample(2);
+        assert_eq!(sampled.frames.len(), 2);
+        assert_eq!(sampled.frames[0].caption, "step 0");
+        assert_eq!(sampled.frames[1].caption, "step 2");
+        assert_eq!(sampled.delay, Duration::from_millis(150));
+
+        assert_eq!(animation().sample(1).frames[0].caption, "step 2");
+        assert_eq!(animation().sample(10), animation());
+    }
+
+    #[test]
+    fn sampling() {
+        let kept = |count, max| -> Vec<usize> {
+            let sampling = Sampling::new(count, max);
+            (0..count).filter(|&index| sampling.keeps(index)).collect()
+        };
+        assert_eq!(kept(10, 4), [0, 3, 6, 9]);
+        assert_eq!(kept(11423, 3), [0, 5711, 11422]);
+        assert_eq!(kept(5, 1), [4]);
+        assert_eq!(kept(3, 0), [0, 1, 2]);
+        assert_eq!(kept(3, 5), [0, 1, 2]);
+        for (count, max) in [(7, 3), (100, 7), (1000, 999), (11423, 500)] {
+            assert_eq!(kept(count, max).len(), Sampling::new(count, max).kept());
+        }
+        assert_eq!(
+            Sampling::new(11423, 500).delay(Duration::from_millis(50)),
+            Duration::from_millis(50) * 11423 / 500
+        );
+    }
+
+    #[test]
+    fn svgs() {
+        let svg = svg(&animation());
+        assert!(svg.starts_with("<svg "));
+        assert_eq!(svg.matches("<animate ").count(), 3);
+        assert!(svg.contains(r#"values="visible;hidden" keyTimes="0;0.3333333333333333""#));
+        assert!(svg.contains(r##"fill="#ffff66""##));
+        assert!(svg.contains(">step 1</text>"));
+        assert!(svg.contains(">A</text>"));
+
+        let mut still = Vec::new();
+        export(&animation(), Format::Svg, &mut still).unwrap();
+        let still = String::from_utf8(still).unwrap();
+        assert!(!still.contains("<animate "));
+        assert!(still.contains(">step 2</text>"));
+    }
+
+    #[test]
+    fn pngs() {
+        let mut png = Vec::new();
+        export(&animation(), Format::Apng, &mut png).unwrap();
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let control = reader.info().animation_control.unwrap();
+        assert_eq!(control.num_frames, 3);
+        // Cells of 8 pixels, the gold one first on the top row
+        assert_eq!((reader.info().width, reader.info().height), (24, 16));
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(pixels[..3], [0xff, 0xff, 0x66]);
+        assert_eq!(pixels[8 * 3..8 * 3 + 3], [0x0f, 0x0f, 0x23]);
+
+        let mut empty = Vec::new();
+        export(
+            &Animation::new(Vec::new(), Duration::ZERO),
+            Format::Apng,
+            &mut empty,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!("animated-svg".parse(), Ok(Format::AnimatedSvg));
+        assert!("gif".parse::<Format>().is_err());
+        assert_eq!(Format::from_extension("PNG"), Some(Format::Apng));
+        assert_eq!(Format::from_extension("svg"), Some(Format::AnimatedSvg));
+        assert_eq!(Format::from_extension("gif"), None);
+    }
+}
@@ -0,0 +1,368 @@
+//! Every item handed out again among some elves, so that the one carrying
+//! the most carries as little as possible: multiway number partitioning.
+//!
+//! Few items are split exactly; more with the longest processing time
+//! first (LPT) rule, the biggest item first to the least loaded elf, whose
+//! plan is within a known factor of the best one.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::{self, Display};
+
+use serde::Serialize;
+
+use crate::elfs::Elf;
+
+/// Items up to which the best plan is searched for, which can take a while
+/// with more of them.
+pub const EXACT_ITEMS: usize = 16;
+
+/// Who carries what once the items are handed out again.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Plan {
+    /// Whether no plan has a smaller maximum load.
+    pub exact: bool,
+    // Loads can add up to more than a u64 holds, unlike each elf's own
+    pub max_load: u128,
+    /// The maximum load no plan can do better than.
+    pub lower_bound: u128,
+    /// How many times the best plan's maximum load this one's is at most,
+    /// 1 when exact.
+    pub bound: f64,
+    /// One for each elf, the first one being 1, up to as many elves as
+    /// items: the others carry nothing.
+    pub loads: Vec<Load>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Load {
+    pub elf: usize,
+    pub calories: u128,
+    /// Biggest first.
+    pub items: Vec<Item>,
+}
+
+/// An item, with the position of the elf carrying it in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Item {
+    pub from: usize,
+    pub calories: u64,
+}
+
+/// The items of `elfs` handed out among `elves` elves, exactly for up to
+/// [`EXACT_ITEMS`] items and with the LPT rule otherwise. None for no elves.
+///
+/// ```
+/// use day01::elfs::Elf;
+///
+/// let elfs = [Elf::new(1, vec![3, 3]), Elf::new(2, vec![2, 2, 2])];
+/// let plan = day01::balance::plan(&elfs, 2).unwrap();
+/// assert_eq!(plan.max_load, 6);
+/// assert!(plan.exact);
+/// ```
+pub fn plan(elfs: &[Elf], elves: usize) -> Option<Plan> {
+    if elfs.iter().map(Elf::item_count).sum::<usize>() <= EXACT_ITEMS {
+        exact(elfs, elves)
+    } else {
+        greedy(elfs, elves)
+    }
+}
+
+/// The best plan, found by branch and bound from the LPT one.
+pub fn exact(elfs: &[Elf], elves: usize) -> Option<Plan> {
+    let items = items(elfs);
+    let mut best = lpt(&items, elves)?;
+    let mut search = Search {
+        items: &items,
+        lower_bound: lower_bound(&items, elves),
+        loads: vec![0; carrying(&items, elves)],
+        bins: vec![0; items.len()],
+        best_load: max_load(&items, &best),
+        best: &mut best,
+    };
+    search.run(0);
+
+    Some(to_plan(&items, &best, elves, true))
+}
+
+/// The plan of the LPT rule, at most `4/3 - 1/(3 * elves)` times the best
+/// plan's maximum load.
+pub fn greedy(elfs: &[Elf], elves: usize) -> Option<Plan> {
+    let items = items(elfs);
+    let bins = lpt(&items, elves)?;
+    Some(to_plan(&items, &bins, elves, false))
+}
+
+// Biggest first, then in the input's order
+fn items(elfs: &[Elf]) -> Vec<Item> {
+    let mut items: Vec<Item> = elfs
+        .iter()
+        .flat_map(|elf| {
+            elf.items().iter().map(|&calories| Item {
+                from: elf.position(),
+                calories,
+            })
+        })
+        .collect();
+    items.sort_by_key(|item| Reverse(item.calories));
+    items
+}
+
+// The elves that can be handed an item, the first ones: with more elves
+// than items, the others would carry nothing
+fn carrying(items: &[Item], elves: usize) -> usize {
+    elves.min(items.len())
+}
+
+// The elf each of the sorted `items` goes to
+fn lpt(items: &[Item], elves: usize) -> Option<Vec<usize>> {
+    if elves == 0 {
+        return None;
+    }
+    // The least loaded elf on top, the first one among equals
+    let mut loads: BinaryHeap<Reverse<(u128, usize)>> = (0..carrying(items, elves))
+        .map(|elf| Reverse((0, elf)))
+        .collect();
+    let bins = items
+        .iter()
+        .map(|item| {
+            let Reverse((load, elf)) = loads.pop().expect("There are elves");
+            loads.push(Reverse((load + item.calories as u128, elf)));
+            elf
+        })
+        .collect();
+    Some(bins)
+}
+
+// The biggest item, an even share, and the two items one of the elves has
+// to carry when there are more items than elves
+fn lower_bound(items: &[Item], elves: usize) -> u128 {
+    let total: u128 = items.iter().map(|item| item.calories as u128).sum();
+    let pair = match items.get(elves) {
+        Some(item) => items[elves - 1].calories as u128 + item.calories as u128,
+        None => 0,
+    };
+    let biggest = items.first().map_or(0, |item| item.calories as u128);
+    total.div_ceil(elves as u128).max(biggest).max(pair)
+}
+
+fn max_load(items: &[Item], bins: &[usize]) -> u128 {
+    let mut loads = vec![0; items.len()];
+    for (item, &bin) in items.iter().zip(bins) {
+        loads[bin] += item.calories as u128;
+    }
+    loads.into_iter().max().unwrap_or(0)
+}
+
+struct Search<'a> {
+    items: &'a [Item],
+    lower_bound: u128,
+    loads: Vec<u128>,
+    bins: Vec<usize>,
+    best_load: u128,
+    best: &'a mut Vec<usize>,
+}
+
+impl Search<'_> {
+    fn run(&mut self, index: usize) {
+        // Nothing beats a plan as good as the lower bound
+        if self.best_load == self.lower_bound {
+            return;
+        }
+        let Some(item) = self.items.get(index) else {
+            let max = self.loads.iter().copied().max().unwrap_or(0);
+            if max < self.best_load {
+                self.best_load = max;
+                self.best.clone_from(&self.bins);
+            }
+            return;
+        };
+
+        for elf in 0..self.loads.len() {
+            let load = self.loads[elf] + item.calories as u128;
+            // Elves as loaded as one already tried would lead to the same
+            // plans, the other way around
+            if load >= self.best_load || self.loads[..elf].contains(&self.loads[elf]) {
+                continue;
+            }
+            self.loads[elf] = load;
+            self.bins[index] = elf;
+            self.run(index + 1);
+            self.loads[elf] -= item.calories as u128;
+        }
+    }
+}
+
+fn to_plan(items: &[Item], bins: &[usize], elves: usize, exact: bool) -> Plan {
+    let mut loads: Vec<Load> = (1..=carrying(items, elves))
+        .map(|elf| Load {
+            elf,
+            calories: 0,
+            items: Vec::new(),
+        })
+        .collect();
+    for (&item, &bin) in items.iter().zip(bins) {
+        loads[bin].calories += item.calories as u128;
+        loads[bin].items.push(item);
+    }
+
+    Plan {
+        exact,
+        max_load: loads.iter().map(|load| load.calories).max().unwrap_or(0),
+        lower_bound: lower_bound(items, elves),
+        bound: if exact {
+            1.0
+        } else {
+            4.0 / 3.0 - 1.0 / (3.0 * elves as f64)
+        },
+        loads,
+    }
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.exact {
+            writeln!(f, "max load {}, the best one", self.max_load)?;
+        } else {
+            writeln!(
+                f,
+                "max load {}, at most {:.3} times the best one, which is at least {}",
+                self.max_load, self.bound, self.lower_bound
+            )?;
+        }
+        for Load {
+            elf,
+            calories,
+            items,
+        } in &self.loads
+        {
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| format!("{} (elf {})", item.calories, item.from))
+                .collect();
+            writeln!(f, "elf {elf:<4}{calories:>8}: {}", items.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elves;
+
+    fn calories(plan: &Plan) -> Vec<u128> {
+        plan.loads.iter().map(|load| load.calories).collect()
+    }
+
+    #[test]
+    fn exact_beats_lpt() {
+        // LPT gives 3 + 2 + 2 and 3 + 2, the best is 3 + 3 and 2 + 2 + 2
+        let elfs = elves(&[&[3, 2], &[3, 2, 2]]);
+        assert_eq!(greedy(&elfs, 2).unwrap().max_load, 7);
+
+        let plan = exact(&elfs, 2).unwrap();
+        assert!(plan.exact);
+        assert_eq!(calories(&plan), [6, 6]);
+        assert_eq!((plan.max_load, plan.lower_bound, plan.bound), (6, 6, 1.0));
+        assert_eq!(plan, super::plan(&elfs, 2).unwrap());
+    }
+
+    #[test]
+    fn every_item_once() {
+        let elfs = elves(&[
+            &[1000, 2000, 3000],
+            &[4000],
+            &[5000, 6000],
+            &[7000, 8000, 9000],
+            &[10000],
+        ]);
+        let plan = exact(&elfs, 3).unwrap();
+        assert_eq!((plan.max_load, plan.lower_bound), (19000, 18334));
+
+        let mut items: Vec<Item> = plan
+            .loads
+            .iter()
+            .flat_map(|load| load.items.clone())
+            .collect();
+        items.sort_by_key(|item| (item.from, item.calories));
+        let given: Vec<(usize, u64)> = items
+            .iter()
+            .map(|item| (item.from, item.calories))
+            .collect();
+        let mut carried: Vec<(usize, u64)> = elfs
+            .iter()
+            .flat_map(|elf| {
+                elf.items()
+                    .iter()
+                    .map(|&calories| (elf.position(), calories))
+            })
+            .collect();
+        carried.sort();
+        assert_eq!(given, carried);
+    }
+
+    #[test]
+    fn greedy_bound() {
+        let items: Vec<u64> = (1..=100).map(|item| item * 37 % 1000 + 1).collect();
+        let elfs = elves(&[&items]);
+        let plan = super::plan(&elfs, 7).unwrap();
+        assert!(!plan.exact);
+        assert_eq!(plan.loads.len(), 7);
+        assert_eq!(
+            calories(&plan).iter().sum::<u128>(),
+            items.iter().sum::<u64>() as u128
+        );
+        assert!(plan.lower_bound <= plan.max_load);
+        assert_eq!(plan.bound, 4.0 / 3.0 - 1.0 / 21.0);
+
+        // The bound is on the best plan's maximum load, known on few items
+        let items: Vec<u64> = (1..=12).map(|item| item * 37 % 100 + 1).collect();
+        let elfs = elves(&[&items]);
+        for elves in 1..=5 {
+            let greedy = greedy(&elfs, elves).unwrap();
+            let best = exact(&elfs, elves).unwrap().max_load;
+            assert!(best <= greedy.max_load);
+            assert!(greedy.max_load as f64 <= greedy.bound * best as f64);
+        }
+    }
+
+    #[test]
+    fn more_elves_than_items() {
+        let elfs = elves(&[&[5, 1], &[]]);
+        let plan = super::plan(&elfs, 4).unwrap();
+        assert_eq!(calories(&plan), [5, 1]);
+        assert_eq!(plan.lower_bound, 5);
+        // Only the elves carrying something are ever counted
+        let plan = super::plan(&elfs, usize::MAX).unwrap();
+        assert_eq!((calories(&plan), plan.lower_bound), (vec![5, 1], 5));
+        assert_eq!(greedy(&elfs, usize::MAX).unwrap().max_load, 5);
+        assert_eq!(super::plan(&elfs, 0), None);
+        assert_eq!(super::plan(&[], 2).unwrap().max_load, 0);
+    }
+
+    #[test]
+    fn huge_loads() {
+        let max = u64::MAX;
+        let elfs = elves(&[&[max], &[max, 0], &[max]]);
+        let plan = super::plan(&elfs, 2).unwrap();
+        assert_eq!(
+            (plan.max_load, plan.lower_bound),
+            (2 * max as u128, 2 * max as u128)
+        );
+        assert_eq!(greedy(&elfs, 1).unwrap().max_load, 3 * max as u128);
+    }
+
+    #[test]
+    fn text() {
+        let elfs = elves(&[
+            &[1000, 2000, 3000],
+            &[4000],
+            &[5000, 6000],
+            &[7000, 8000, 9000],
+            &[10000],
+        ]);
+        common::assert_snapshot!("balance", exact(&elfs, 3).unwrap().to_string());
+        common::assert_snapshot!("balance-greedy", greedy(&elfs, 3).unwrap().to_string());
+    }
+}
@@ -0,0 +1,51 @@
+use common::{Generator, Rng};
+
+use crate::Day01;
+
+// Each elf is a group of calories, with a blank line after it
+impl Generator for Day01 {
+    const SIZE: usize = 250;
+
+    // `size` is the number of elves, at least 3 for the top three
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+
+        for _ in 0..size.max(3) {
+            for _ in 0..rng.range(1..=15) {
+                input += &format!("{}\n", rng.range(1000..=60_000));
+            }
+            input.push('\n');
+        }
+
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_are_valid() {
+        for seed in 0..20 {
+            let input = common::generate::<Day01>(seed as usize, seed);
+            let elfs = Day01::parse(&input).unwrap();
+            assert_eq!(elfs.len(), (seed as usize).max(3));
+            assert!(Day01::part1(&elfs, &Default::default()).is_some());
+            assert!(Day01::part2(&elfs, &Default::default()).is_some());
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(
+            common::generate::<Day01>(50, 1),
+            common::generate::<Day01>(50, 1)
+        );
+        assert_ne!(
+            common::generate::<Day01>(50, 1),
+            common::generate::<Day01>(50, 2)
+        );
+    }
+}
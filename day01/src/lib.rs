@@ -0,0 +1,338 @@
+//! Day 1: Calorie Counting.
+//!
+//! The input lists the calories of the food each elf carries,
+//! one elf after the other, separated by blank lines.
+//!
+//! ```
+//! use common::Solution;
+//! use day01::Day01;
+//!
+//! let elfs = Day01::parse("1000\n2000\n\n4000\n\n500\n\n").unwrap();
+//! assert_eq!(elfs[0].calories(), 3000);
+//! assert_eq!(elfs[0].items(), [1000, 2000]);
+//! assert_eq!(Day01::top_calories(&elfs, 2), Some(7000));
+//! ```
+
+use common::{ParseError, Solution};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::BufRead;
+
+pub mod balance;
+mod generate;
+pub mod stats;
+pub mod top;
+
+pub mod elfs {
+    /// An elf, with the calories of each item of food it carries, in the
+    /// order of the input.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Elf {
+        position: usize,
+        items: Vec<u64>,
+        // Sum of the items, which most queries are about
+        calories: u64,
+    }
+
+    impl Elf {
+        /// The elf at `position` in the input, the first one being 1. Its
+        /// items add up to at most `u64::MAX`, as [`Day01::elves`] checks.
+        ///
+        /// [`Day01::elves`]: crate::Day01::elves
+        pub fn new(position: usize, items: Vec<u64>) -> Elf {
+            let calories = items.iter().sum();
+            Elf {
+                position,
+                items,
+                calories,
+            }
+        }
+
+        pub fn position(&self) -> usize {
+            self.position
+        }
+
+        pub fn items(&self) -> &[u64] {
+            &self.items
+        }
+
+        pub fn item_count(&self) -> usize {
+            self.items.len()
+        }
+
+        pub fn calories(&self) -> u64 {
+            self.calories
+        }
+
+        /// Calories of the elf's biggest item, None when it has none.
+        pub fn largest_item(&self) -> Option<u64> {
+            self.items.iter().copied().max()
+        }
+
+        /// Whether one of the elf's items has exactly these calories.
+        pub fn carries(&self, calories: u64) -> bool {
+            self.items.contains(&calories)
+        }
+    }
+
+    /// The elves with an item of exactly `calories`, in the input's order.
+    pub fn carrying(elfs: &[Elf], calories: u64) -> impl Iterator<Item = &Elf> {
+        elfs.iter().filter(move |elf| elf.carries(calories))
+    }
+}
+
+use elfs::Elf;
+
+/// Knobs of the puzzle, the puzzle's own values by default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Options {
+    /// How many of the elves carrying the most are added up in part 2.
+    pub top_qnt: usize,
+    /// Percentiles of the calories in the details' statistics, from 0 to
+    /// 100.
+    pub percentiles: Vec<f64>,
+    /// Most bars in the histogram of the details' statistics.
+    pub histogram_bins: usize,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            top_qnt: 3,
+            percentiles: vec![10.0, 25.0, 75.0, 90.0, 99.0],
+            histogram_bins: 10,
+        }
+    }
+}
+
+pub struct Day01;
+
+impl Day01 {
+    /// The elves of `input` one at a time, read as they are needed. An elf
+    /// carrying more calories than a `u64` holds is an error at the item
+    /// going over.
+    pub fn elves(input: impl BufRead) -> impl Iterator<Item = Result<Elf, ParseError>> {
+        common::groups(input).enumerate().map(|(index, group)| {
+            let mut calories: u64 = 0;
+            let items = group?
+                .into_iter()
+                .map(|(number, line)| {
+                    let item = common::parse_line(&line, "calories")
+                        .map_err(|err| err.relocate(number, 1))?;
+                    calories = calories.checked_add(item).ok_or_else(|| {
+                        let column = common::fields(&line).next().map_or(1, |(column, _)| column);
+                        let message = format!("the elf carries more than {} calories", u64::MAX);
+                        ParseError::new(number, column, message)
+                    })?;
+                    Ok(item)
+                })
+                .collect::<Result<_, ParseError>>()?;
+            Ok(Elf::new(index + 1, items))
+        })
+    }
+
+    /// The `top_qnt` elves of `input` carrying the most, most first, or all
+    /// of them when there are fewer. Only those are kept in memory, however
+    /// long the input.
+    pub fn top_elves_of(input: impl BufRead, top_qnt: usize) -> Result<Vec<Elf>, ParseError> {
+        let mut top = top::Top::new(top_qnt);
+        for elf in Day01::elves(input) {
+            top.push(elf?);
+        }
+        Ok(top.into_sorted_vec())
+    }
+
+    /// Calories carried by the `top_qnt` elves carrying the most, or by
+    /// all of them when there are fewer. None when there are no elves.
+    pub fn top_calories(elfs: &[Elf], top_qnt: usize) -> Option<u128> {
+        if elfs.is_empty() {
+            return None;
+        }
+        // Only each elf's own calories fit in a u64
+        let calories = top::top(elfs, top_qnt)
+            .iter()
+            .map(|elf| elf.calories() as u128)
+            .sum();
+        Some(calories)
+    }
+}
+
+impl Solution for Day01 {
+    type Input = Vec<Elf>;
+    type Options = Options;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    // An elf per group of lines, each line being an item's calories
+    fn read(input: impl BufRead) -> Result<Vec<Elf>, ParseError> {
+        Day01::elves(input).collect()
+    }
+
+    fn part1(elfs: &Vec<Elf>, _options: &Options) -> Option<u128> {
+        Day01::top_calories(elfs, 1)
+    }
+
+    fn part2(elfs: &Vec<Elf>, options: &Options) -> Option<u128> {
+        Day01::top_calories(elfs, options.top_qnt)
+    }
+
+    fn details(elfs: &Vec<Elf>, options: &Options) -> Option<Value> {
+        let top_elves: Vec<Value> = top::top(elfs, options.top_qnt)
+            .into_iter()
+            .map(|elf| json!({ "elf": elf.position(), "calories": elf.calories() }))
+            .collect();
+        let statistics = stats::Report::new(elfs, &options.percentiles, options.histogram_bins);
+        Some(json!({ "top_elves": top_elves, "statistics": statistics }))
+    }
+}
+
+/// Elves carrying these items, numbered from 1.
+#[cfg(test)]
+fn elves(items: &[&[u64]]) -> Vec<Elf> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, items)| Elf::new(index + 1, items.to_vec()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn details() {
+        let elfs = Day01::parse("1000\n\n5000\n\n2000\n3000\n\n500\n\n").unwrap();
+        let details = Day01::details(&elfs, &Default::default()).unwrap();
+        assert_eq!(
+            details["top_elves"],
+            json!([
+                { "elf": 2, "calories": 5000 },
+                { "elf": 3, "calories": 5000 },
+                { "elf": 1, "calories": 1000 },
+            ])
+        );
+
+        let options = Options {
+            percentiles: vec![50.0],
+            histogram_bins: 2,
+            ..Default::default()
+        };
+        let statistics = &Day01::details(&elfs, &options).unwrap()["statistics"];
+        assert_eq!(statistics["count"], 4);
+        assert_eq!(statistics["median"], 3000.0);
+        assert_eq!(
+            statistics["percentiles"],
+            json!([{ "percentile": 50.0, "calories": 3000.0 }])
+        );
+        assert_eq!(
+            statistics["histogram"],
+            json!([
+                { "from": 500, "to": 2750, "elves": 2 },
+                { "from": 2751, "to": 5000, "elves": 2 },
+            ])
+        );
+        assert_eq!(
+            Day01::details(&Vec::new(), &options).unwrap()["statistics"],
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn items() {
+        let elfs = Day01::parse("1000\n2000\n\n5000\n\n2000\n3000\n\n").unwrap();
+        assert_eq!(elfs[0].items(), [1000, 2000]);
+        assert_eq!(elfs[2].position(), 3);
+        assert_eq!(elfs[2].item_count(), 2);
+        assert_eq!(elfs[2].calories(), 5000);
+        assert_eq!(elfs[2].largest_item(), Some(3000));
+        assert_eq!(Elf::new(4, Vec::new()).largest_item(), None);
+
+        let carrying: Vec<usize> = elfs::carrying(&elfs, 2000).map(Elf::position).collect();
+        assert_eq!(carrying, [1, 3]);
+        assert_eq!(elfs::carrying(&elfs, 2500).count(), 0);
+    }
+
+    #[test]
+    fn top_qnt() {
+        let elfs = Day01::parse("1000\n\n5000\n\n2000\n3000\n\n500\n\n").unwrap();
+        assert_eq!(
+            Day01::part2(
+                &elfs,
+                &Options {
+                    top_qnt: 2,
+                    ..Default::default()
+                }
+            ),
+            Some(10000)
+        );
+        assert_eq!(
+            Day01::part2(
+                &elfs,
+                &Options {
+                    top_qnt: 5,
+                    ..Default::default()
+                }
+            ),
+            Some(11500)
+        );
+        assert_eq!(Day01::part2(&Vec::new(), &Default::default()), None);
+    }
+
+    #[test]
+    fn streaming_top() {
+        let input = common::generate::<Day01>(100_000, 1);
+        let top = Day01::top_elves_of(input.as_bytes(), 3).unwrap();
+        assert_eq!(top.len(), 3);
+
+        let elfs = Day01::parse(&input).unwrap();
+        assert_eq!(elfs.len(), 100_000);
+        assert_eq!(
+            top,
+            top::top(&elfs, 3).into_iter().cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            top.iter().map(|elf| elf.calories() as u128).sum::<u128>(),
+            Day01::part2(&elfs, &Default::default()).unwrap()
+        );
+
+        let all = Day01::top_elves_of("1000\n\n3000\n".as_bytes(), 5).unwrap();
+        assert_eq!(all.iter().map(Elf::position).collect::<Vec<_>>(), [2, 1]);
+        assert!(Day01::top_elves_of("1000\n\nx\n".as_bytes(), 5).is_err());
+    }
+
+    #[test]
+    fn last_elf() {
+        let elfs = Day01::parse("1000\n\n\n\n2000\r\n3000").unwrap();
+        assert_eq!(elfs.len(), 2);
+        assert_eq!(elfs[1].position(), 2);
+        assert_eq!(elfs[1].calories(), 5000);
+        assert_eq!(Day01::part1(&elfs, &Default::default()), Some(5000));
+    }
+
+    #[test]
+    fn huge_calories() {
+        let max = u64::MAX;
+        assert_eq!(
+            Day01::parse(&format!("1\n\n{max}\n  1\n")).unwrap_err(),
+            ParseError::new(4, 3, format!("the elf carries more than {max} calories"))
+        );
+
+        let elfs = Day01::parse(&format!("{max}\n\n{max}\n\n{max}\n")).unwrap();
+        assert_eq!(Day01::part1(&elfs, &Default::default()), Some(max as u128));
+        assert_eq!(
+            Day01::part2(&elfs, &Default::default()),
+            Some(3 * max as u128)
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        let error = Day01::parse("1000\n2000\n\n3k00\n").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(4, 1, "expected calories, found `3k00`")
+        );
+    }
+}
@@ -0,0 +1,368 @@
+//! How the calories are spread across the elves: averages, percentiles, a
+//! histogram and the elves carrying unusually little or much.
+
+use std::fmt::{self, Display};
+
+use serde::{Serialize, Serializer};
+
+use crate::elfs::Elf;
+
+// Width of the histogram's longest bar, in characters
+const BAR_WIDTH: usize = 40;
+
+/// Statistics of the calories the elves carry, which serialize to JSON and
+/// print as text, the histogram drawn with bars.
+///
+/// ```
+/// use common::Solution;
+/// use day01::{stats::Report, Day01};
+///
+/// let elfs = Day01::parse("1000\n\n5000\n\n2000\n3000\n").unwrap();
+/// let report = Report::new(&elfs, &[50.0], 2).unwrap();
+/// assert!(report.to_string().starts_with("elves    3\ntotal    11000\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub count: usize,
+    // More than a u64 holds for enough elves carrying a lot, which JSON
+    // values only take as a float
+    #[serde(serialize_with = "serialize_total")]
+    pub total: u128,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    /// Of the whole population of elves, not of a sample.
+    pub std_dev: f64,
+    pub percentiles: Vec<Percentile>,
+    pub histogram: Vec<Bin>,
+    pub outliers: Vec<Outlier>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Percentile {
+    pub percentile: f64,
+    pub calories: f64,
+}
+
+/// The elves carrying from `from` calories to `to`, both included.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Bin {
+    pub from: u64,
+    pub to: u64,
+    pub elves: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Low,
+    High,
+}
+
+/// An elf carrying more than 1.5 interquartile ranges away from the
+/// quartiles, the usual fences of a box plot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Outlier {
+    pub elf: usize,
+    pub calories: u64,
+    pub side: Side,
+}
+
+impl Report {
+    /// The report on `elfs`, with the given `percentiles`, from 0 to 100,
+    /// and `bins` bars in the histogram at most. None without elves.
+    ///
+    /// Percentiles out of range are left out, and fall between the
+    /// calories of two elves when no elf is exactly at them.
+    pub fn new(elfs: &[Elf], percentiles: &[f64], bins: usize) -> Option<Report> {
+        let mut calories: Vec<u64> = elfs.iter().map(Elf::calories).collect();
+        calories.sort_unstable();
+        let (&min, &max) = (calories.first()?, calories.last()?);
+
+        let count = calories.len();
+        let total: u128 = calories.iter().map(|&calories| calories as u128).sum();
+        let mean = total as f64 / count as f64;
+        let variance = calories
+            .iter()
+            .map(|&calories| (calories as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        let (q1, q3) = (percentile(&calories, 25.0), percentile(&calories, 75.0));
+        let fence = 1.5 * (q3 - q1);
+        let outliers = elfs
+            .iter()
+            .filter_map(|elf| {
+                let side = match elf.calories() as f64 {
+                    calories if calories < q1 - fence => Side::Low,
+                    calories if calories > q3 + fence => Side::High,
+                    _ => return None,
+                };
+                Some(Outlier {
+                    elf: elf.position(),
+                    calories: elf.calories(),
+                    side,
+                })
+            })
+            .collect();
+
+        Some(Report {
+            count,
+            total,
+            min,
+            max,
+            mean,
+            median: percentile(&calories, 50.0),
+            std_dev: variance.sqrt(),
+            percentiles: percentiles
+                .iter()
+                .filter(|percentile| (0.0..=100.0).contains(*percentile))
+                .map(|&p| Percentile {
+                    percentile: p,
+                    calories: percentile(&calories, p),
+                })
+                .collect(),
+            histogram: histogram(&calories, bins),
+            outliers,
+        })
+    }
+}
+
+fn serialize_total<S: Serializer>(total: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+    match u64::try_from(*total) {
+        Ok(total) => serializer.serialize_u64(total),
+        Err(_) => serializer.serialize_f64(*total as f64),
+    }
+}
+
+// Linearly interpolated between the closest ranks of the sorted `calories`
+fn percentile(calories: &[u64], percentile: f64) -> f64 {
+    let rank = percentile / 100.0 * (calories.len() - 1) as f64;
+    let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+    let (below, above) = (calories[below] as f64, calories[above] as f64);
+    below + (above - below) * rank.fract()
+}
+
+// Bins of the same width from the least calories to the most, the last one
+// ending at the most. There can be one more calorie from the least to the
+// most than a u64 holds.
+fn histogram(calories: &[u64], bins: usize) -> Vec<Bin> {
+    let (min, max) = (calories[0], calories[calories.len() - 1]);
+    if bins == 0 {
+        return Vec::new();
+    }
+    let span = (max - min) as u128 + 1;
+    let width = span.div_ceil(bins as u128);
+    let bins = span.div_ceil(width) as usize;
+
+    let mut elves = vec![0; bins];
+    for &calories in calories {
+        elves[((calories - min) as u128 / width) as usize] += 1;
+    }
+    elves
+        .into_iter()
+        .enumerate()
+        .map(|(index, elves)| {
+            // Both at most `max`, so back in a u64
+            let from = min as u128 + index as u128 * width;
+            Bin {
+                from: from as u64,
+                to: (from + width - 1).min(max as u128) as u64,
+                elves,
+            }
+        })
+        .collect()
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "elves    {}", self.count)?;
+        writeln!(f, "total    {}", self.total)?;
+        writeln!(f, "min      {}", self.min)?;
+        writeln!(f, "max      {}", self.max)?;
+        writeln!(f, "mean     {:.1}", self.mean)?;
+        writeln!(f, "median   {:.1}", self.median)?;
+        writeln!(f, "std dev  {:.1}", self.std_dev)?;
+        for Percentile {
+            percentile,
+            calories,
+        } in &self.percentiles
+        {
+            writeln!(f, "{:<9}{calories:.1}", format!("p{percentile}"))?;
+        }
+
+        if !self.histogram.is_empty() {
+            writeln!(f, "\nhistogram")?;
+            let most = self
+                .histogram
+                .iter()
+                .map(|bin| bin.elves)
+                .max()
+                .unwrap_or(0);
+            let digits = self.max.to_string().len() + 1;
+            for Bin { from, to, elves } in &self.histogram {
+                // Every bin with elves gets a bar, however short
+                let bar = (elves * BAR_WIDTH).div_ceil(most.max(1));
+                writeln!(
+                    f,
+                    "{from:>digits$} - {to:<digits$}|{} {elves}",
+                    "#".repeat(bar)
+                )?;
+            }
+        }
+
+        if !self.outliers.is_empty() {
+            writeln!(f, "\noutliers")?;
+            for Outlier {
+                elf,
+                calories,
+                side,
+            } in &self.outliers
+            {
+                let side = match side {
+                    Side::Low => "low",
+                    Side::High => "high",
+                };
+                writeln!(f, "elf {elf:<6}{calories:>8}  {side}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elves;
+    use serde_json::json;
+
+    #[test]
+    fn summary() {
+        let elfs = elves(&[&[6000], &[4000], &[11000], &[24000], &[10000]]);
+        let report = Report::new(&elfs, &[0.0, 25.0, 90.0, 100.0, 101.0], 4).unwrap();
+        assert_eq!((report.count, report.total), (5, 55000));
+        assert_eq!((report.min, report.max), (4000, 24000));
+        assert_eq!(report.mean, 11000.0);
+        assert_eq!(report.median, 10000.0);
+        assert_eq!(report.std_dev, 48_800_000f64.sqrt());
+
+        let percentiles: Vec<(f64, f64)> = report
+            .percentiles
+            .iter()
+            .map(|p| (p.percentile, p.calories))
+            .collect();
+        assert_eq!(
+            percentiles,
+            [
+                (0.0, 4000.0),
+                (25.0, 6000.0),
+                (90.0, 18800.0),
+                (100.0, 24000.0)
+            ]
+        );
+
+        assert_eq!(
+            report.outliers,
+            [Outlier {
+                elf: 4,
+                calories: 24000,
+                side: Side::High
+            }]
+        );
+        assert_eq!(Report::new(&[], &[50.0], 4), None);
+    }
+
+    #[test]
+    fn histogram() {
+        let elfs = elves(&[&[6000], &[4000], &[11000], &[24000], &[10000]]);
+        let bins: Vec<(u64, u64, usize)> = Report::new(&elfs, &[], 4)
+            .unwrap()
+            .histogram
+            .iter()
+            .map(|bin| (bin.from, bin.to, bin.elves))
+            .collect();
+        assert_eq!(
+            bins,
+            [
+                (4000, 9000, 2),
+                (9001, 14001, 2),
+                (14002, 19002, 0),
+                (19003, 24000, 1),
+            ]
+        );
+
+        let same = Report::new(&elves(&[&[7], &[7], &[7]]), &[], 10).unwrap();
+        assert_eq!(
+            same.histogram,
+            [Bin {
+                from: 7,
+                to: 7,
+                elves: 3
+            }]
+        );
+        assert!(same.outliers.is_empty());
+        assert!(Report::new(&elfs, &[], 0).unwrap().histogram.is_empty());
+    }
+
+    #[test]
+    fn extremes() {
+        let report = Report::new(&elves(&[&[0], &[u64::MAX], &[u64::MAX]]), &[], 2).unwrap();
+        assert_eq!(report.total, 2 * u64::MAX as u128);
+        assert_eq!(
+            report.histogram,
+            [
+                Bin {
+                    from: 0,
+                    to: u64::MAX / 2,
+                    elves: 1
+                },
+                Bin {
+                    from: u64::MAX / 2 + 1,
+                    to: u64::MAX,
+                    elves: 2
+                },
+            ]
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["total"], json!(2.0 * u64::MAX as f64));
+        let report = Report::new(&elves(&[&[u64::MAX]]), &[], 0).unwrap();
+        assert_eq!(
+            serde_json::to_value(&report).unwrap()["total"],
+            json!(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn outliers() {
+        let elfs = elves(&[&[100], &[5000], &[5100], &[5200], &[5300], &[5400], &[9000]]);
+        let report = Report::new(&elfs, &[], 0).unwrap();
+        let outliers: Vec<(usize, Side)> = report
+            .outliers
+            .iter()
+            .map(|outlier| (outlier.elf, outlier.side))
+            .collect();
+        assert_eq!(outliers, [(1, Side::Low), (7, Side::High)]);
+        assert_eq!(
+            serde_json::to_value(&report.outliers[0]).unwrap(),
+            json!({ "elf": 1, "calories": 100, "side": "low" })
+        );
+    }
+
+    #[test]
+    fn text() {
+        let elfs = elves(&[
+            &[6000],
+            &[4000],
+            &[11000],
+            &[24000],
+            &[10000],
+            &[7500],
+            &[9000],
+            &[8000],
+        ]);
+        let report = Report::new(&elfs, &[25.0, 50.0, 99.5], 5).unwrap();
+        common::assert_snapshot!("stats", report.to_string());
+    }
+}
@@ -0,0 +1,141 @@
+//! The elves carrying the most, picked as they come, without keeping the
+//! others around.
+
+use std::borrow::Borrow;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use crate::elfs::Elf;
+
+// Ordered from the elf carrying the least to the one carrying the most,
+// the first in the input winning ties
+struct Ranked<E>(E);
+
+impl<E: Borrow<Elf>> Ranked<E> {
+    fn key(&self) -> (u64, Reverse<usize>) {
+        let elf = self.0.borrow();
+        (elf.calories(), Reverse(elf.position()))
+    }
+}
+
+impl<E: Borrow<Elf>> PartialEq for Ranked<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl<E: Borrow<Elf>> Eq for Ranked<E> {}
+
+impl<E: Borrow<Elf>> PartialOrd for Ranked<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E: Borrow<Elf>> Ord for Ranked<E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// The `k` elves carrying the most of the ones pushed so far, in memory
+/// for `k` elves only. Elves are either owned or borrowed.
+pub struct Top<E> {
+    k: usize,
+    // The elf carrying the least of the kept ones on top, to be replaced
+    heap: BinaryHeap<Reverse<Ranked<E>>>,
+}
+
+impl<E: Borrow<Elf>> Top<E> {
+    pub fn new(k: usize) -> Top<E> {
+        Top {
+            k,
+            heap: BinaryHeap::with_capacity(k.saturating_add(1).min(1024)),
+        }
+    }
+
+    pub fn push(&mut self, elf: E) {
+        if self.k == 0 {
+            return;
+        }
+        self.heap.push(Reverse(Ranked(elf)));
+        if self.heap.len() > self.k {
+            self.heap.pop();
+        }
+    }
+
+    /// How many elves are kept, `k` unless fewer were pushed.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// The kept elves, the one carrying the most first, then the first
+    /// one in the input among those carrying as much.
+    pub fn into_sorted_vec(self) -> Vec<E> {
+        // Sorted from the least of the `Reverse`s, which is the most
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(Ranked(elf))| elf)
+            .collect()
+    }
+}
+
+/// The `k` elves of `elves` carrying the most, most first, or all of them
+/// when there are fewer.
+///
+/// ```
+/// use day01::elfs::Elf;
+///
+/// let elves = [Elf::new(1, vec![3]), Elf::new(2, vec![7]), Elf::new(3, vec![5])];
+/// let top: Vec<usize> = day01::top::top(&elves, 2).iter().map(|elf| elf.position()).collect();
+/// assert_eq!(top, [2, 3]);
+/// assert_eq!(day01::top::top(&elves, 10).len(), 3);
+/// ```
+pub fn top<E: Borrow<Elf>>(elves: impl IntoIterator<Item = E>, k: usize) -> Vec<E> {
+    let mut top = Top::new(k);
+    for elf in elves {
+        top.push(elf);
+    }
+    top.into_sorted_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elves;
+
+    fn positions(elves: &[&Elf]) -> Vec<usize> {
+        elves.iter().map(|elf| elf.position()).collect()
+    }
+
+    #[test]
+    fn most_first() {
+        let elves = elves(&[&[6000], &[4000], &[11000], &[24000], &[10000]]);
+        assert_eq!(positions(&top(&elves, 3)), [4, 3, 5]);
+        assert_eq!(positions(&top(&elves, 1)), [4]);
+        assert!(top(&elves, 0).is_empty());
+    }
+
+    #[test]
+    fn ties() {
+        let elves = elves(&[&[5000], &[1000], &[5000], &[5000]]);
+        assert_eq!(positions(&top(&elves, 2)), [1, 3]);
+        assert_eq!(positions(&top(elves.iter().rev(), 2)), [1, 3]);
+    }
+
+    #[test]
+    fn fewer_elves_than_k() {
+        let elves = elves(&[&[1000], &[3000]]);
+        assert_eq!(positions(&top(&elves, 5)), [2, 1]);
+        assert!(top(Vec::<Elf>::new(), 3).is_empty());
+
+        let mut top = Top::new(usize::MAX);
+        top.push(&elves[0]);
+        assert_eq!(top.len(), 1);
+    }
+}
@@ -0,0 +1,45 @@
+use common::{Generator, Rng};
+
+use crate::Day02;
+
+impl Generator for Day02 {
+    const SIZE: usize = 2500;
+
+    // `size` is the number of rounds in the strategy guide
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+
+        for _ in 0..size {
+            let opponent = rng.pick(&['A', 'B', 'C']);
+            let second = rng.pick(&['X', 'Y', 'Z']);
+            input += &format!("{opponent} {second}\n");
+        }
+
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_are_valid() {
+        for seed in 0..20 {
+            let input = common::generate::<Day02>(seed as usize * 10, seed);
+            let rounds = Day02::parse(&input).unwrap();
+            assert_eq!(rounds.len(), seed as usize * 10);
+            assert!(Day02::part1(&rounds, &Default::default()).is_some());
+            assert!(Day02::part2(&rounds, &Default::default()).is_some());
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(
+            common::generate::<Day02>(50, 1),
+            common::generate::<Day02>(50, 1)
+        );
+    }
+}
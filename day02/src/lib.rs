@@ -0,0 +1,232 @@
+//! Day 2: Rock Paper Scissors.
+//!
+//! The input is a strategy guide, with the opponent's play and a letter
+//! whose meaning changes between the two parts on each line.
+//!
+//! ```
+//! use day02::rock_paper_scissors::Play;
+//!
+//! let opponent = Play::from_opponent('A').unwrap();
+//! let player = Play::from_player('Y').unwrap();
+//! assert_eq!(player.total_points(&opponent), 8);
+//!
+//! let player = Play::from_outcome_char('Y', &opponent).unwrap();
+//! assert_eq!(player, Play::Rock);
+//! ```
+
+use common::{NoOptions, ParseError, Solution};
+use std::io::BufRead;
+
+mod generate;
+
+pub mod rock_paper_scissors {
+    use std::cmp::Ordering;
+
+    /// A play, which is greater than the play it beats.
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum Play {
+        Rock,
+        Paper,
+        Scissors,
+    }
+
+    use Play::{Paper, Rock, Scissors};
+
+    impl PartialOrd for Play {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Play {
+        fn cmp(&self, other: &Self) -> Ordering {
+            match (self, other) {
+                (Rock, Paper) | (Paper, Scissors) | (Scissors, Rock) => Ordering::Less,
+                (Rock, Rock) | (Paper, Paper) | (Scissors, Scissors) => Ordering::Equal,
+                (Rock, Scissors) | (Paper, Rock) | (Scissors, Paper) => Ordering::Greater,
+            }
+        }
+    }
+
+    impl Play {
+        /// Decodes the opponent's column: A, B or C.
+        pub fn from_opponent(letter: char) -> Option<Play> {
+            match letter {
+                'A' => Some(Play::Rock),
+                'B' => Some(Play::Paper),
+                'C' => Some(Play::Scissors),
+                _ => None,
+            }
+        }
+
+        /// Decodes the second column as a play: X, Y or Z.
+        pub fn from_player(letter: char) -> Option<Play> {
+            match letter {
+                'X' => Some(Play::Rock),
+                'Y' => Some(Play::Paper),
+                'Z' => Some(Play::Scissors),
+                _ => None,
+            }
+        }
+
+        /// The play that gets `outcome` against the opponent's play.
+        pub fn from_outcome(outcome: Ordering, opponent: &Play) -> Play {
+            match (outcome, opponent) {
+                (Ordering::Less, Paper)
+                | (Ordering::Equal, Rock)
+                | (Ordering::Greater, Scissors) => Rock,
+                (Ordering::Less, Scissors)
+                | (Ordering::Equal, Paper)
+                | (Ordering::Greater, Rock) => Paper,
+                (Ordering::Less, Rock)
+                | (Ordering::Equal, Scissors)
+                | (Ordering::Greater, Paper) => Scissors,
+            }
+        }
+
+        /// Decodes the second column as the outcome to get:
+        /// X to lose, Y to draw and Z to win.
+        pub fn from_outcome_char(letter: char, opponent: &Play) -> Option<Play> {
+            match letter {
+                'X' => Some(Play::from_outcome(Ordering::Less, opponent)),
+                'Y' => Some(Play::from_outcome(Ordering::Equal, opponent)),
+                'Z' => Some(Play::from_outcome(Ordering::Greater, opponent)),
+                _ => None,
+            }
+        }
+
+        fn points_from_variant(&self) -> u64 {
+            match self {
+                Rock => 1,
+                Paper => 2,
+                Scissors => 3,
+            }
+        }
+
+        /// Points of a round: the play's own points and the outcome's.
+        pub fn total_points(&self, opponent: &Self) -> u64 {
+            let round_points = match self.cmp(opponent) {
+                Ordering::Less => 0,
+                Ordering::Equal => 3,
+                Ordering::Greater => 6,
+            };
+
+            round_points + self.points_from_variant()
+        }
+    }
+}
+
+use rock_paper_scissors::Play;
+
+pub struct Day02;
+
+impl Day02 {
+    // A column of the strategy guide, which should be a single letter
+    fn letter(
+        field: Option<(usize, &str)>,
+        line: usize,
+        end: usize,
+    ) -> Result<(usize, char), ParseError> {
+        let (column, field) =
+            field.ok_or_else(|| ParseError::new(line, end, "expected a letter"))?;
+
+        let mut chars = field.chars();
+        match (chars.next(), chars.next()) {
+            (Some(letter), None) => Ok((column, letter)),
+            _ => {
+                let message = format!("expected a single letter, found `{field}`");
+                Err(ParseError::new(line, column, message))
+            }
+        }
+    }
+
+    fn total_points(
+        rounds: &[(Play, char)],
+        player: impl Fn(char, &Play) -> Option<Play>,
+    ) -> Option<u64> {
+        let mut points: u64 = 0;
+        for (opponent, letter) in rounds {
+            points += player(*letter, opponent)?.total_points(opponent);
+        }
+        Some(points)
+    }
+}
+
+impl Solution for Day02 {
+    // Each round is the opponent's play and the still undecoded second column
+    type Input = Vec<(Play, char)>;
+    type Options = NoOptions;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn read(input: impl BufRead) -> Result<Vec<(Play, char)>, ParseError> {
+        let mut rounds = Vec::new();
+
+        for line in common::lines(input) {
+            let (number, line) = line?;
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut fields = common::fields(&line);
+            let end = line.chars().count() + 1;
+
+            let (column, letter) = Day02::letter(fields.next(), number, end)?;
+            let opponent = Play::from_opponent(letter).ok_or_else(|| {
+                ParseError::new(
+                    number,
+                    column,
+                    format!("expected A, B or C, found `{letter}`"),
+                )
+            })?;
+
+            let (column, letter) = Day02::letter(fields.next(), number, end)?;
+            if !matches!(letter, 'X' | 'Y' | 'Z') {
+                let message = format!("expected X, Y or Z, found `{letter}`");
+                return Err(ParseError::new(number, column, message));
+            }
+
+            if let Some((column, field)) = fields.next() {
+                let message = format!("expected the end of the line, found `{field}`");
+                return Err(ParseError::new(number, column, message));
+            }
+
+            rounds.push((opponent, letter));
+        }
+
+        Ok(rounds)
+    }
+
+    fn part1(rounds: &Vec<(Play, char)>, _options: &NoOptions) -> Option<u64> {
+        Day02::total_points(rounds, |letter, _| Play::from_player(letter))
+    }
+
+    fn part2(rounds: &Vec<(Play, char)>, _options: &NoOptions) -> Option<u64> {
+        Day02::total_points(rounds, Play::from_outcome_char)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Day02::parse("A Y\nD X").unwrap_err(),
+            ParseError::new(2, 1, "expected A, B or C, found `D`")
+        );
+        assert_eq!(
+            Day02::parse("A Y\nB  W").unwrap_err(),
+            ParseError::new(2, 4, "expected X, Y or Z, found `W`")
+        );
+        assert_eq!(
+            Day02::parse("C").unwrap_err(),
+            ParseError::new(1, 2, "expected a letter")
+        );
+        assert_eq!(
+            Day02::parse("C ZZ").unwrap_err(),
+            ParseError::new(1, 3, "expected a single letter, found `ZZ`")
+        );
+    }
+}
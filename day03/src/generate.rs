@@ -0,0 +1,97 @@
+use common::{Generator, Rng};
+
+use crate::Day03;
+
+impl Day03 {
+    // Both compartments only share `shared`, and `badge` is in one of them
+    fn rucksack(rng: &mut Rng, letters: &[char], shared: char, badge: char) -> String {
+        let (left_letters, right_letters) = letters.split_at(letters.len() / 2);
+        let len = rng.range(2..=16) as usize;
+
+        let mut left = vec![shared];
+        let mut right = vec![shared];
+        if badge != shared {
+            if rng.chance(1, 2) {
+                left.push(badge);
+            } else {
+                right.push(badge);
+            }
+        }
+
+        for (compartment, letters) in [(&mut left, left_letters), (&mut right, right_letters)] {
+            while compartment.len() < len {
+                compartment.push(*rng.pick(letters));
+            }
+            rng.shuffle(compartment);
+        }
+
+        left.into_iter().chain(right).collect()
+    }
+}
+
+impl Generator for Day03 {
+    const SIZE: usize = 100;
+
+    // `size` is the number of groups of three rucksacks. Each group only
+    // shares its badge, because every rucksack of the group takes its
+    // other letters from a different third of the alphabet.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+
+        for _ in 0..size {
+            let mut letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+            rng.shuffle(&mut letters);
+            let (badge, letters) = letters.split_first().expect("There are 52 letters");
+
+            for letters in letters.chunks(letters.len() / 3) {
+                let (shared, others) = if rng.chance(1, 4) {
+                    (*badge, letters)
+                } else {
+                    (letters[0], &letters[1..])
+                };
+                input += &Day03::rucksack(rng, others, shared, *badge);
+                input.push('\n');
+            }
+        }
+
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_are_valid() {
+        for seed in 0..20 {
+            let input = common::generate::<Day03>(seed as usize, seed);
+            let rucksacks = Day03::parse(&input).unwrap();
+            assert_eq!(rucksacks.len(), seed as usize * 3);
+            assert!(Day03::part1(&rucksacks, &Default::default()).is_some());
+            assert!(Day03::part2(&rucksacks, &Default::default()).is_some());
+        }
+    }
+
+    #[test]
+    fn one_shared_item_and_one_badge() {
+        let input = common::generate::<Day03>(50, 1);
+        let lines: Vec<&str> = input.lines().collect();
+
+        for line in &lines {
+            let (left, right) = line.split_at(line.len() / 2);
+            let mut shared: Vec<char> = left.chars().filter(|&c| right.contains(c)).collect();
+            shared.dedup();
+            assert_eq!(shared.len(), 1, "{line}");
+        }
+
+        for group in lines.chunks(3) {
+            let badges = group[0]
+                .chars()
+                .filter(|&c| group[1].contains(c) && group[2].contains(c))
+                .collect::<std::collections::HashSet<_>>();
+            assert_eq!(badges.len(), 1, "{group:?}");
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Day 3: Rucksack Reorganization.
+//!
+//! The input lists the items of each rucksack, as letters, half of them
+//! in each compartment. Groups of three elves share a badge item.
+//!
+//! ```
+//! use day03::rsack::Rucksack;
+//!
+//! let rucksacks = Rucksack::from_input("abcA\nBaCa\naDxy".as_bytes()).unwrap();
+//! assert_eq!(rucksacks[0].left(), ['a', 'b']);
+//! assert_eq!(rucksacks[1].priority(), Some(1));
+//! assert_eq!(Rucksack::group_priority(&rucksacks), Some(1));
+//! ```
+
+use common::{NoOptions, ParseError, Solution};
+use std::io::BufRead;
+
+mod generate;
+
+pub mod rsack {
+    use common::ParseError;
+    use std::io::BufRead;
+
+    static LETTERS: [char; 52] = [
+        'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r',
+        's', 't', 'u', 'v', 'w', 'x', 'y', 'z', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J',
+        'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+    ];
+
+    /// The items of a rucksack, each a letter.
+    #[derive(Debug, Clone)]
+    pub struct Rucksack {
+        items: Vec<char>,
+    }
+
+    impl Rucksack {
+        pub fn from(items: Vec<char>) -> Rucksack {
+            Rucksack { items }
+        }
+
+        /// Items of the first compartment.
+        pub fn left(&self) -> &[char] {
+            let middle = self.items.len() / 2;
+            &self.items[0..middle]
+        }
+
+        /// Items of the second compartment.
+        pub fn right(&self) -> &[char] {
+            let middle = self.items.len() / 2;
+            &self.items[middle..]
+        }
+
+        // Checks every item of the left compartment against the right one,
+        // which is slow but obviously right
+        #[cfg(test)]
+        fn shared_chars(&self) -> Option<Vec<char>> {
+            let mut shared: Vec<char> = Vec::new();
+
+            for character in self.left() {
+                if self.right().contains(character) && !shared.contains(character) {
+                    shared.push(character.to_owned());
+                }
+            }
+            if !shared.is_empty() {
+                Some(shared)
+            } else {
+                None
+            }
+        }
+
+        // One bit per letter, the bit of priority p being 1 << (p - 1),
+        // so that shared letters are a bitwise and away
+        fn letter_mask(items: &[char]) -> u64 {
+            let mut mask = 0;
+            for item in items {
+                if let Some(priority) = Rucksack::char_priority(item) {
+                    mask |= 1 << (priority - 1);
+                }
+            }
+            mask
+        }
+
+        fn mask_priority(mut mask: u64) -> u64 {
+            let mut priority = 0;
+            while mask != 0 {
+                priority += mask.trailing_zeros() as u64 + 1;
+                mask &= mask - 1;
+            }
+            priority
+        }
+
+        fn char_priority(letter: &char) -> Option<u64> {
+            match letter {
+                'a'..='z' => Some(*letter as u64 - 'a' as u64 + 1),
+                'A'..='Z' => Some(*letter as u64 - 'A' as u64 + 27),
+                _ => None,
+            }
+        }
+
+        /// Sum of the priorities of the items in both compartments,
+        /// or None when they share none.
+        pub fn priority(&self) -> Option<u64> {
+            let shared = Rucksack::letter_mask(self.left()) & Rucksack::letter_mask(self.right());
+            if shared == 0 {
+                return None;
+            }
+            Some(Rucksack::mask_priority(shared))
+        }
+
+        #[cfg(test)]
+        fn priority_naive(&self) -> Option<u64> {
+            let mut priority = 0;
+            // The ? operator will early return None if shared_chars is None
+            for character in self.shared_chars()? {
+                if let Some(char_priority) = Rucksack::char_priority(&character) {
+                    priority += char_priority;
+                }
+            }
+            Some(priority)
+        }
+
+        /// A rucksack per line of the input.
+        pub fn from_input(input: impl BufRead) -> Result<Vec<Rucksack>, ParseError> {
+            let mut rucksacks: Vec<Rucksack> = Vec::new();
+
+            for line in common::lines(input) {
+                let (number, line) = line?;
+                for (column, item) in line.chars().enumerate() {
+                    if !LETTERS.contains(&item) {
+                        let message = format!("expected an item letter, found `{item}`");
+                        return Err(ParseError::new(number, column + 1, message));
+                    }
+                }
+                rucksacks.push(Rucksack::from(line.chars().collect()));
+            }
+
+            Ok(rucksacks)
+        }
+
+        #[cfg(test)]
+        fn group_shared_chars(group: &[Rucksack]) -> Vec<char> {
+            let mut shared: Vec<char> = Vec::new();
+            for chr in &group[0].items {
+                let mut is_shared = group[1].items.contains(chr);
+
+                for rucksack in group {
+                    is_shared &= rucksack.items.contains(chr)
+                }
+
+                if is_shared && !shared.contains(chr) {
+                    shared.push(chr.to_owned());
+                }
+            }
+            shared
+        }
+
+        /// Sum of the priorities of the items every rucksack of the group
+        /// has, or None for an empty group.
+        pub fn group_priority(group: &[Rucksack]) -> Option<u64> {
+            let shared = group
+                .iter()
+                .map(|rucksack| Rucksack::letter_mask(&rucksack.items))
+                .reduce(|shared, mask| shared & mask)?;
+            Some(Rucksack::mask_priority(shared))
+        }
+
+        #[cfg(test)]
+        fn group_priority_naive(group: &[Rucksack]) -> Option<u64> {
+            let shared = Rucksack::group_shared_chars(group);
+            let mut priority = 0;
+
+            for chr in &shared {
+                // Will propagate None if it appears
+                priority += Rucksack::char_priority(chr)?;
+            }
+            Some(priority)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use proptest::prelude::*;
+
+        #[test]
+        fn char_priorities() {
+            assert_eq!(
+                Rucksack::char_priority(&'a').expect("'a' char has a priority"),
+                1
+            );
+            assert_eq!(
+                Rucksack::char_priority(&'p').expect("'p' char has a priority"),
+                16
+            );
+            assert_eq!(
+                Rucksack::char_priority(&'L').expect("'L' char has a priority"),
+                38
+            );
+            assert_eq!(
+                Rucksack::char_priority(&'P').expect("'P' char has a priority"),
+                42
+            );
+            assert_eq!(
+                Rucksack::char_priority(&'t').expect("'t' char has a priority"),
+                20
+            );
+            assert_eq!(
+                Rucksack::char_priority(&'v').expect("'v' char has a priority"),
+                22
+            );
+            assert_eq!(
+                Rucksack::char_priority(&'s').expect("'s' char has a priority"),
+                19
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn char_without_priority() {
+            Rucksack::char_priority(&'*').unwrap();
+        }
+
+        #[test]
+        fn group_shared_chars() {
+            let group = vec![
+                Rucksack::from(vec!['a', 'b', 'c', 'd']),
+                Rucksack::from(vec!['b', 'c', 'd']),
+                Rucksack::from(vec!['a', 'b']),
+                Rucksack::from(vec!['b', 'e', 'f', 'g']),
+            ];
+
+            assert_eq!(Rucksack::group_shared_chars(&group), vec!['b']);
+        }
+
+        fn rucksack() -> impl Strategy<Value = Rucksack> {
+            // Few letters, so that compartments often share some of them
+            "[a-eA-E]{0,12}".prop_map(|items| Rucksack::from(items.chars().collect()))
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(2000))]
+
+            #[test]
+            fn same_priority_as_naive(rucksack in rucksack()) {
+                prop_assert_eq!(rucksack.priority(), rucksack.priority_naive());
+            }
+
+            #[test]
+            fn same_group_priority_as_naive(group in prop::collection::vec(rucksack(), 3)) {
+                prop_assert_eq!(
+                    Rucksack::group_priority(&group),
+                    Rucksack::group_priority_naive(&group)
+                );
+            }
+
+            #[test]
+            fn same_char_priority_as_letters(index in 0usize..52) {
+                prop_assert_eq!(Rucksack::char_priority(&LETTERS[index]), Some(index as u64 + 1));
+            }
+        }
+    }
+}
+
+use rsack::Rucksack;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Rucksack>;
+    type Options = NoOptions;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn read(input: impl BufRead) -> Result<Vec<Rucksack>, ParseError> {
+        Rucksack::from_input(input)
+    }
+
+    fn part1(rucksacks: &Vec<Rucksack>, _options: &NoOptions) -> Option<u64> {
+        let mut priorities = 0;
+        for rucksack in rucksacks {
+            priorities += rucksack.priority()?;
+        }
+        Some(priorities)
+    }
+
+    fn part2(rucksacks: &Vec<Rucksack>, _options: &NoOptions) -> Option<u64> {
+        let mut priorities = 0;
+        for group in rucksacks.chunks_exact(3) {
+            priorities += Rucksack::group_priority(group)?;
+        }
+        Some(priorities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn test_input() -> Vec<Rucksack> {
+        let input = fs::read_to_string("data/test-input.txt").unwrap();
+        Day03::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn part1() {
+        assert_eq!(Day03::part1(&test_input(), &Default::default()), Some(157));
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(Day03::part2(&test_input(), &Default::default()), Some(70));
+    }
+
+    #[test]
+    fn parse_error() {
+        assert_eq!(
+            Day03::parse("vJrwpWtwJgWr\nhcsFMMf-FFhFp").unwrap_err(),
+            ParseError::new(2, 8, "expected an item letter, found `-`")
+        );
+    }
+}
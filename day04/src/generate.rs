@@ -0,0 +1,49 @@
+use common::{Generator, Rng};
+
+use crate::Day04;
+
+impl Generator for Day04 {
+    const SIZE: usize = 1000;
+
+    // `size` is the number of pairs, with sections between 1 and 99
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+
+        for _ in 0..size {
+            let a = rng.range(1..=99);
+            let b = rng.range(a..=99);
+            let c = rng.range(1..=99);
+            let d = rng.range(c..=99);
+            input += &format!("{a}-{b},{c}-{d}\n");
+        }
+
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_are_valid() {
+        for seed in 0..20 {
+            let input = common::generate::<Day04>(seed as usize * 10, seed);
+            let pairs = Day04::parse(&input).unwrap();
+            assert_eq!(pairs.len(), seed as usize * 10);
+            assert!(
+                Day04::part1(&pairs, &Default::default())
+                    <= Day04::part2(&pairs, &Default::default())
+            );
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(
+            common::generate::<Day04>(50, 1),
+            common::generate::<Day04>(50, 1)
+        );
+    }
+}
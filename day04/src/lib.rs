@@ -0,0 +1,231 @@
+//! Day 4: Camp Cleanup.
+//!
+//! The input lists pairs of elves with the range of sections each of them
+//! is assigned, like `2-4,6-8`.
+//!
+//! ```
+//! use day04::{Assignment, AssignmentPair};
+//!
+//! let assignment: Assignment = "3-7".parse().unwrap();
+//! assert!(assignment.contains(&"4-5".parse().unwrap()));
+//!
+//! let pairs = AssignmentPair::from_input("2-4,6-8\n2-8,3-7".as_bytes()).unwrap();
+//! assert_eq!(AssignmentPair::count_contained(&pairs), 1);
+//! assert_eq!(AssignmentPair::count_overlapped(&pairs), 1);
+//! ```
+
+use common::{NoOptions, ParseError, Solution};
+use std::io::BufRead;
+use std::str::FromStr;
+
+mod generate;
+
+/// The sections an elf is assigned, from `start` to `end` included.
+#[derive(Debug)]
+pub struct Assignment {
+    start: u64,
+    end: u64,
+}
+
+/// The assignments of a pair of elves.
+#[derive(Debug)]
+pub struct AssignmentPair(Assignment, Assignment);
+
+impl FromStr for Assignment {
+    type Err = ParseError;
+
+    fn from_str(string: &str) -> Result<Assignment, ParseError> {
+        let (start, end) = string.split_once('-').ok_or_else(|| {
+            ParseError::new(
+                1,
+                string.chars().count() + 1,
+                "expected `-` after the first section",
+            )
+        })?;
+        let end_column = start.chars().count() + 2;
+        let (start, end) = (
+            Assignment::section(start, 1)?,
+            Assignment::section(end, end_column)?,
+        );
+        if start > end {
+            let message = format!("expected a section from {start} on, found {end}");
+            return Err(ParseError::new(1, end_column, message));
+        }
+        Ok(Assignment { start, end })
+    }
+}
+
+impl Assignment {
+    fn section(string: &str, column: usize) -> Result<u64, ParseError> {
+        string.parse().map_err(|_| {
+            ParseError::new(
+                1,
+                column,
+                format!("expected a section number, found `{string}`"),
+            )
+        })
+    }
+
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Whether every section of `other` is in this assignment.
+    pub fn contains(&self, other: &Self) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    /// Whether the assignments have at least a section in common.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !((self.start > other.end) || (other.start > self.end))
+    }
+}
+
+impl AssignmentPair {
+    /// A pair per line of the input.
+    pub fn from_input(input: impl BufRead) -> Result<Vec<AssignmentPair>, ParseError> {
+        let mut pairs: Vec<AssignmentPair> = Vec::new();
+
+        for line in common::lines(input) {
+            let (number, line) = line?;
+            let (a, b) = line.split_once(',').ok_or_else(|| {
+                let message = "expected `,` after the first assignment";
+                ParseError::new(number, line.chars().count() + 1, message)
+            })?;
+            let b_column = a.chars().count() + 2;
+            let pair = AssignmentPair(
+                a.parse()
+                    .map_err(|err: ParseError| err.relocate(number, 1))?,
+                b.parse()
+                    .map_err(|err: ParseError| err.relocate(number, b_column))?,
+            );
+            pairs.push(pair);
+        }
+        Ok(pairs)
+    }
+
+    pub fn first(&self) -> &Assignment {
+        &self.0
+    }
+
+    pub fn second(&self) -> &Assignment {
+        &self.1
+    }
+
+    /// Whether one of the assignments contains the other.
+    pub fn has_contained(&self) -> bool {
+        self.0.contains(&self.1) || self.1.contains(&self.0)
+    }
+
+    pub fn count_contained(pairs: &[AssignmentPair]) -> u64 {
+        let mut count = 0;
+        for pair in pairs {
+            if pair.has_contained() {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    pub fn has_overlapped(&self) -> bool {
+        self.0.overlaps(&self.1) || self.1.overlaps(&self.0)
+    }
+
+    pub fn count_overlapped(pairs: &[AssignmentPair]) -> u64 {
+        let mut count = 0;
+        for pair in pairs {
+            if pair.has_overlapped() {
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<AssignmentPair>;
+    type Options = NoOptions;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn read(input: impl BufRead) -> Result<Vec<AssignmentPair>, ParseError> {
+        AssignmentPair::from_input(input)
+    }
+
+    fn part1(pairs: &Vec<AssignmentPair>, _options: &NoOptions) -> Option<u64> {
+        Some(AssignmentPair::count_contained(pairs))
+    }
+
+    fn part2(pairs: &Vec<AssignmentPair>, _options: &NoOptions) -> Option<u64> {
+        Some(AssignmentPair::count_overlapped(pairs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn contains() {
+        assert!(Assignment { start: 0, end: 0 }.contains(&Assignment { start: 0, end: 0 }));
+        assert!(Assignment { start: 3, end: 5 }.contains(&Assignment { start: 3, end: 4 }));
+        assert!(Assignment { start: 5, end: 10 }.contains(&Assignment { start: 6, end: 9 }));
+        assert!(!Assignment { start: 5, end: 6 }.contains(&Assignment { start: 4, end: 6 }));
+        assert!(!Assignment { start: 7, end: 8 }.contains(&Assignment { start: 6, end: 8 }));
+    }
+
+    #[test]
+    fn overlaps() {
+        assert!(Assignment { start: 0, end: 0 }.overlaps(&Assignment { start: 0, end: 0 }));
+        assert!(Assignment { start: 3, end: 5 }.overlaps(&Assignment { start: 3, end: 4 }));
+        assert!(Assignment { start: 5, end: 10 }.overlaps(&Assignment { start: 6, end: 9 }));
+        assert!(Assignment { start: 5, end: 6 }.overlaps(&Assignment { start: 4, end: 6 }));
+        assert!(Assignment { start: 7, end: 8 }.overlaps(&Assignment { start: 6, end: 8 }));
+    }
+
+    fn test_input() -> Vec<AssignmentPair> {
+        let input = fs::read_to_string("data/test-input.txt").unwrap();
+        Day04::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn part1() {
+        assert_eq!(Day04::part1(&test_input(), &Default::default()), Some(2));
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(Day04::part2(&test_input(), &Default::default()), Some(4));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Day04::parse("2-4,6-8\n2-3 4-5").unwrap_err(),
+            ParseError::new(2, 8, "expected `,` after the first assignment")
+        );
+        assert_eq!(
+            Day04::parse("2-4,6-8\n2-3,4+5").unwrap_err(),
+            ParseError::new(2, 8, "expected `-` after the first section")
+        );
+        assert_eq!(
+            Day04::parse("2-4,6-x8").unwrap_err(),
+            ParseError::new(1, 7, "expected a section number, found `x8`")
+        );
+        assert_eq!(
+            Day04::parse("4-2,1-3").unwrap_err(),
+            ParseError::new(1, 3, "expected a section from 4 on, found 2")
+        );
+        assert_eq!(
+            Day04::parse("2-4,6-8\n1-3,13-12").unwrap_err(),
+            ParseError::new(2, 8, "expected a section from 13 on, found 12")
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use common::{Generator, Rng};
+
+use crate::Day05;
+
+const STACKS: usize = 9;
+
+impl Generator for Day05 {
+    const SIZE: usize = 500;
+
+    // `size` is the number of moves. The ship has 9 stacks, none of them
+    // empty at first so they are all drawn, and every move only takes
+    // crates that are still on the stack.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut heights: Vec<u64> = (0..STACKS).map(|_| rng.range(1..=8)).collect();
+        let letters: Vec<char> = ('A'..='Z').collect();
+        let mut input = String::new();
+
+        let top = *heights.iter().max().expect("The ship has stacks");
+        for level in (1..=top).rev() {
+            let row: Vec<String> = heights
+                .iter()
+                .map(|&height| {
+                    if height >= level {
+                        format!("[{}]", rng.pick(&letters))
+                    } else {
+                        String::from("   ")
+                    }
+                })
+                .collect();
+            input += &row.join(" ");
+            input.push('\n');
+        }
+
+        let numbers: Vec<String> = (1..=STACKS).map(|number| format!(" {number} ")).collect();
+        input += &numbers.join(" ");
+        input += "\n\n";
+
+        for _ in 0..size {
+            let from = loop {
+                let from = rng.index(STACKS);
+                if heights[from] > 0 {
+                    break from;
+                }
+            };
+            let to = (from + 1 + rng.index(STACKS - 1)) % STACKS;
+            let repeat = rng.range(1..=heights[from].min(u8::MAX as u64));
+
+            heights[from] -= repeat;
+            heights[to] += repeat;
+            input += &format!("move {repeat} from {} to {}\n", from + 1, to + 1);
+        }
+
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_are_valid() {
+        for seed in 0..20 {
+            let input = common::generate::<Day05>(seed as usize * 10, seed);
+            let (ship, moves) = Day05::parse(&input).unwrap();
+            assert_eq!(moves.len(), seed as usize * 10);
+
+            let part1 = Day05::part1(&(ship.clone(), moves.clone()), &Default::default()).unwrap();
+            let part2 = Day05::part2(&(ship, moves), &Default::default()).unwrap();
+            assert!(!part1.is_empty());
+            assert_eq!(part1.len(), part2.len());
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(
+            common::generate::<Day05>(50, 1),
+            common::generate::<Day05>(50, 1)
+        );
+    }
+}
@@ -0,0 +1,575 @@
+//! Day 5: Supply Stacks.
+//!
+//! The input is a drawing of the stacks of crates on a ship, then the
+//! procedure moving them around, one crate or many at once.
+//!
+//! ```
+//! use day05::{Move, Ship};
+//!
+//! let drawing = "[A]    \n[B] [C]\n 1   2 ";
+//! let ship: Ship = drawing.parse().unwrap();
+//! let procedure = "move 2 from 1 to 2".as_bytes();
+//! let moves = Move::from_procedure(procedure, ship.stack_count()).unwrap();
+//!
+//! let mut one_at_a_time = ship.clone();
+//! one_at_a_time.move_crates_from_procedure(&moves).unwrap();
+//! assert_eq!(one_at_a_time.crates_on_top_as_string(), "B");
+//!
+//! let mut many_at_once = ship.with_crate_mover_9001();
+//! many_at_once.move_crates_from_procedure(&moves).unwrap();
+//! assert_eq!(many_at_once.crates_on_top_as_string(), "A");
+//! ```
+
+use common::trace::Level;
+use common::visual::{Cell, Color, Frame};
+use common::{Animation, NoOptions, ParseError, Part, Solution};
+use regex::Regex;
+use std::io::BufRead;
+use std::str::FromStr;
+use std::time::Duration;
+
+mod generate;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Crate(char);
+
+#[derive(Debug, Clone)]
+struct Stack {
+    crates: Vec<Crate>,
+}
+
+impl Stack {
+    pub fn new() -> Stack {
+        Stack { crates: Vec::new() }
+    }
+
+    pub fn on_top(&self) -> Option<&Crate> {
+        self.crates.last()
+    }
+
+    pub fn push(&mut self, crt: Crate) {
+        self.crates.push(crt);
+    }
+
+    pub fn pop(&mut self) -> Option<Crate> {
+        self.crates.pop()
+    }
+
+    pub fn insert(&mut self, index: usize, crt: Crate) {
+        self.crates.insert(index, crt)
+    }
+}
+
+/// A move took more crates than its stack had.
+#[derive(Debug, PartialEq, Eq)]
+pub struct NoMoreCrates;
+
+/// Stacks of crates, moved by a crane that takes one crate at a time,
+/// unless it is the CrateMover 9001.
+#[derive(Debug, Clone)]
+pub struct Ship {
+    stacks: Vec<Stack>,
+    move_many_at_once: bool,
+}
+
+impl Ship {
+    fn new() -> Ship {
+        Ship {
+            stacks: Vec::new(),
+            move_many_at_once: false,
+        }
+    }
+
+    /// The same ship, with a crane moving many crates at once.
+    pub fn with_crate_mover_9001(mut self) -> Ship {
+        self.move_many_at_once = true;
+        self
+    }
+
+    fn on_top(&self, idx: usize) -> Option<&Crate> {
+        let stack = self.stacks.get(idx)?;
+        stack.on_top()
+    }
+
+    pub fn stack_count(&self) -> usize {
+        self.stacks.len()
+    }
+
+    /// Letters of the crates on top of each stack, skipping empty ones.
+    pub fn crates_on_top_as_string(&self) -> String {
+        let mut s = String::new();
+        for stack in &self.stacks {
+            if let Some(crt) = stack.on_top() {
+                s.push(crt.0);
+            }
+        }
+        s
+    }
+
+    fn move_crate(&mut self, from: usize, to: usize) -> Result<(), NoMoreCrates> {
+        let crt = self.stacks[from].pop().ok_or(NoMoreCrates)?;
+        self.stacks[to].push(crt);
+        Ok(())
+    }
+
+    fn move_crates(&mut self, repeat: u8, from: usize, to: usize) -> Result<(), NoMoreCrates> {
+        if !self.move_many_at_once {
+            for _ in 0..repeat {
+                self.move_crate(from, to)?;
+            }
+        } else {
+            // Create temporary stack
+            self.stacks.push(Stack::new());
+            let last_index = self.stacks.len() - 1;
+
+            for _ in 0..repeat {
+                self.move_crate(from, last_index)?;
+            }
+            for _ in 0..repeat {
+                self.move_crate(last_index, to)?;
+            }
+
+            self.stacks.pop();
+        }
+
+        common::event!(Level::Debug, "move", {
+            "count": repeat,
+            "from": from + 1,
+            "to": to + 1,
+            "many_at_once": self.move_many_at_once,
+            "on_top": self.on_top(to).map(|crt| crt.0.to_string()),
+        });
+        Ok(())
+    }
+
+    /// The ship drawn like in the input, with room for `height` crates
+    /// on each stack.
+    pub fn frame(&self, height: usize) -> Frame {
+        let mut frame = Frame::new((self.stacks.len() * 4).saturating_sub(1), height + 1);
+
+        for (index, stack) in self.stacks.iter().enumerate() {
+            let x = index * 4;
+            for (level, crt) in stack.crates.iter().enumerate() {
+                // From green for A to gold for Z
+                let shade = (crt.0 as u8).saturating_sub(b'A') as f64 / 25.0;
+                let color = Color::GREEN.towards(Color::GOLD, shade);
+                let y = height.saturating_sub(level + 1);
+                for (dx, glyph) in ['[', crt.0, ']'].into_iter().enumerate() {
+                    frame.set(x + dx, y, Cell::new(glyph, color));
+                }
+            }
+            for (dx, digit) in (index + 1).to_string().chars().enumerate() {
+                frame.set(x + 1 + dx, height, Cell::new(digit, Color::BACKGROUND));
+            }
+        }
+
+        frame
+    }
+
+    fn crate_count(&self) -> usize {
+        self.stacks.iter().map(|stack| stack.crates.len()).sum()
+    }
+
+    pub fn move_crates_from_procedure(&mut self, moves: &[Move]) -> Result<(), NoMoreCrates> {
+        for mv in moves {
+            self.move_crates(mv.repeat, mv.from, mv.to)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Ship {
+    type Err = ParseError;
+
+    // Only the crates matter, the line of stack numbers below them is
+    // skipped
+    fn from_str(string: &str) -> Result<Ship, ParseError> {
+        let re = Regex::new(r"^\[(\w)\]$").expect("Hardcoded Regex should compile");
+
+        let mut ship = Ship::new();
+
+        for (i, line) in string.lines().enumerate() {
+            let mut fields = common::fields(line).peekable();
+            if fields.peek().is_some() && fields.all(|(_, field)| field.parse::<usize>().is_ok()) {
+                continue;
+            }
+
+            for (column, field) in common::fields(line) {
+                let Some(caps) = re.captures(field) else {
+                    let message = format!("expected a crate like `[A]`, found `{field}`");
+                    return Err(ParseError::new(i + 1, column, message));
+                };
+                // Crates are drawn as `[X] `, so each stack takes 4 columns
+                if column % 4 != 1 {
+                    let message = "crate is not aligned with a stack";
+                    return Err(ParseError::new(i + 1, column, message));
+                }
+
+                let idx = column / 4;
+                let stack = match ship.stacks.get_mut(idx) {
+                    Some(s) => s,
+                    None => {
+                        // Create stacks from their start up to requested index
+                        for _ in ship.stacks.len()..(idx + 1) {
+                            ship.stacks.push(Stack::new());
+                        }
+                        ship.stacks.get_mut(idx).unwrap()
+                    }
+                };
+                let letter = caps[1].chars().next().expect("Regex captured a char");
+                stack.insert(0, Crate(letter));
+            }
+        }
+
+        Ok(ship)
+    }
+}
+
+/// Moving `repeat` crates from a stack to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    repeat: u8,
+    // Stack indices, starting at 0 unlike in the input
+    from: usize,
+    to: usize,
+}
+
+impl Move {
+    /// A move for every line, blank ones aside. Stacks go from 1 to
+    /// `stack_count`.
+    pub fn from_procedure(
+        input: impl BufRead,
+        stack_count: usize,
+    ) -> Result<Vec<Move>, ParseError> {
+        let mut moves = Vec::new();
+
+        for line in common::lines(input) {
+            let (number, line) = line?;
+            if !line.trim().is_empty() {
+                let mv =
+                    Move::from_line(&line, stack_count).map_err(|err| err.relocate(number, 1))?;
+                moves.push(mv);
+            }
+        }
+
+        Ok(moves)
+    }
+
+    fn from_line(line: &str, stack_count: usize) -> Result<Move, ParseError> {
+        let mut fields = common::fields(line);
+        let end = line.chars().count() + 1;
+
+        let (_, repeat) = Move::number(&mut fields, "move", end)?;
+        let (from_column, from) = Move::number(&mut fields, "from", end)?;
+        let (to_column, to) = Move::number(&mut fields, "to", end)?;
+
+        if let Some((column, field)) = fields.next() {
+            let message = format!("expected the end of the line, found `{field}`");
+            return Err(ParseError::new(1, column, message));
+        }
+
+        Ok(Move {
+            repeat,
+            from: Move::stack_index(from, from_column, stack_count)?,
+            to: Move::stack_index(to, to_column, stack_count)?,
+        })
+    }
+
+    // A keyword followed by a number, like `from 3`
+    fn number<'a, T: FromStr>(
+        fields: &mut impl Iterator<Item = (usize, &'a str)>,
+        keyword: &str,
+        end: usize,
+    ) -> Result<(usize, T), ParseError> {
+        match fields.next() {
+            Some((_, field)) if field == keyword => (),
+            Some((column, field)) => {
+                let message = format!("expected `{keyword}`, found `{field}`");
+                return Err(ParseError::new(1, column, message));
+            }
+            None => return Err(ParseError::new(1, end, format!("expected `{keyword}`"))),
+        }
+
+        match fields.next() {
+            Some((column, field)) => match field.parse() {
+                Ok(number) => Ok((column, number)),
+                Err(_) => {
+                    let message = format!("expected a number after `{keyword}`, found `{field}`");
+                    Err(ParseError::new(1, column, message))
+                }
+            },
+            None => {
+                let message = format!("expected a number after `{keyword}`");
+                Err(ParseError::new(1, end, message))
+            }
+        }
+    }
+
+    fn stack_index(number: usize, column: usize, stack_count: usize) -> Result<usize, ParseError> {
+        if (1..=stack_count).contains(&number) {
+            Ok(number - 1)
+        } else {
+            let message = format!("there is no stack {number}, the ship has {stack_count}");
+            Err(ParseError::new(1, column, message))
+        }
+    }
+}
+
+pub struct Day05;
+
+impl Day05 {
+    fn crates_on_top(mut ship: Ship, moves: &[Move]) -> Option<String> {
+        ship.move_crates_from_procedure(moves).ok()?;
+        Some(ship.crates_on_top_as_string())
+    }
+
+    // A frame before the first move and after each of them, up to the
+    // first one taking more crates than there are
+    fn frames(mut ship: Ship, moves: &[Move]) -> Vec<Frame> {
+        let height = ship.crate_count();
+        let mut frames = vec![ship.frame(height).with_caption("start")];
+
+        for mv in moves {
+            if ship.move_crates(mv.repeat, mv.from, mv.to).is_err() {
+                break;
+            }
+            let caption = format!("move {} from {} to {}", mv.repeat, mv.from + 1, mv.to + 1);
+            frames.push(ship.frame(height).with_caption(caption));
+        }
+
+        frames
+    }
+}
+
+impl Solution for Day05 {
+    // The starting ship and the rearrangement procedure below it
+    type Input = (Ship, Vec<Move>);
+    type Options = NoOptions;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn read(mut input: impl BufRead) -> Result<(Ship, Vec<Move>), ParseError> {
+        // The drawing is small, and ends at the first blank line below it
+        let mut drawing = String::new();
+        let mut drawing_lines = 0;
+        for line in common::lines(&mut input) {
+            let (number, line) = line?;
+            drawing_lines = number;
+            if line.trim().is_empty() && !drawing.trim().is_empty() {
+                break;
+            }
+            drawing += &line;
+            drawing.push('\n');
+        }
+
+        let ship = Ship::from_str(&drawing)?;
+        let moves = Move::from_procedure(input, ship.stack_count())
+            .map_err(|err| err.relocate(drawing_lines + 1, 1))?;
+        Ok((ship, moves))
+    }
+
+    fn part1((ship, moves): &(Ship, Vec<Move>), _options: &NoOptions) -> Option<String> {
+        Day05::crates_on_top(ship.clone(), moves)
+    }
+
+    fn part2((ship, moves): &(Ship, Vec<Move>), _options: &NoOptions) -> Option<String> {
+        Day05::crates_on_top(ship.clone().with_crate_mover_9001(), moves)
+    }
+
+    fn animation(
+        (ship, moves): &(Ship, Vec<Move>),
+        part: Part,
+        _options: &NoOptions,
+        // A frame per move, few and small enough to all be drawn
+        _max_frames: usize,
+    ) -> Option<Animation> {
+        let ship = match part {
+            Part::One => ship.clone(),
+            Part::Two => ship.clone().with_crate_mover_9001(),
+        };
+        let frames = Day05::frames(ship, moves);
+        Some(Animation::new(frames, Duration::from_millis(500)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn get_basic_ship() -> Ship {
+        let mut ship = Ship::new();
+        let stack1 = Stack {
+            crates: vec![Crate('a'), Crate('b'), Crate('c')],
+        };
+        let stack2 = Stack {
+            crates: vec![Crate('d'), Crate('e'), Crate('f')],
+        };
+        let stack3 = Stack {
+            crates: vec![Crate('g'), Crate('h')],
+        };
+        ship.stacks = vec![stack1, stack2, stack3];
+        ship
+    }
+
+    fn procedure(commands: &str) -> Vec<Move> {
+        Move::from_procedure(commands.as_bytes(), 3).unwrap()
+    }
+
+    #[test]
+    fn on_top() {
+        let mut stack = Stack {
+            crates: vec![Crate('a'), Crate('b'), Crate('c')],
+        };
+        assert_eq!(stack.on_top(), Some(&Crate('c')));
+        stack.push(Crate('d'));
+        assert_eq!(stack.on_top(), Some(&Crate('d')));
+        stack.pop();
+        stack.pop();
+        stack.pop();
+        assert_eq!(stack.on_top(), Some(&Crate('a')));
+        stack.pop();
+        assert_eq!(stack.on_top(), None);
+    }
+
+    #[test]
+    fn move_crates() {
+        let mut ship = get_basic_ship();
+
+        assert_eq!(ship.on_top(1), Some(&Crate('f')));
+
+        ship.move_crate(0, 1).unwrap();
+        assert_eq!(ship.on_top(1), Some(&Crate('c')));
+
+        ship.move_crate(0, 1).unwrap();
+        assert_eq!(ship.on_top(1), Some(&Crate('b')));
+
+        ship.move_crate(0, 1).unwrap();
+        assert_eq!(ship.on_top(1), Some(&Crate('a')));
+
+        ship.move_crate(1, 0).unwrap();
+
+        ship.move_crate(2, 0).unwrap();
+        assert_eq!(ship.on_top(0), Some(&Crate('h')));
+
+        ship.move_crate(2, 0).unwrap();
+        assert_eq!(ship.on_top(0), Some(&Crate('g')));
+
+        assert!(ship.move_crate(2, 0).is_err());
+
+        ship.move_crates(3, 0, 1).unwrap();
+        assert_eq!(ship.on_top(1), Some(&Crate('a')));
+
+        assert!(ship.move_crates(3, 0, 1).is_err());
+    }
+
+    #[test]
+    fn command() {
+        let mut ship = get_basic_ship();
+
+        ship.move_crates_from_procedure(&procedure("move 3 from 1 to 3"))
+            .unwrap();
+        assert_eq!(ship.on_top(0), None);
+        assert_eq!(ship.on_top(2), Some(&Crate('a')));
+
+        ship.move_crates_from_procedure(&procedure("move 5 from 3 to 2"))
+            .unwrap();
+        assert_eq!(ship.on_top(0), None);
+        assert_eq!(ship.on_top(1), Some(&Crate('g')));
+        assert_eq!(ship.on_top(2), None);
+
+        ship.move_crates_from_procedure(&procedure("move 8 from 2 to 1"))
+            .unwrap();
+        assert_eq!(ship.on_top(0), Some(&Crate('d')));
+        assert_eq!(ship.on_top(1), None);
+        assert_eq!(ship.on_top(2), None);
+    }
+
+    #[test]
+    fn ship_from_file() {
+        let ship = Ship::from_str(
+            "
+    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3
+        ",
+        )
+        .unwrap();
+        assert_eq!(ship.on_top(0), Some(&Crate('N')));
+        assert_eq!(ship.on_top(1), Some(&Crate('D')));
+        assert_eq!(ship.on_top(2), Some(&Crate('P')));
+        assert_eq!(ship.on_top(3), None);
+    }
+
+    fn test_input() -> (Ship, Vec<Move>) {
+        let string = fs::read_to_string("data/test-input.txt").unwrap();
+        Day05::parse(&string).unwrap()
+    }
+
+    #[test]
+    fn part1() {
+        assert_eq!(
+            Day05::part1(&test_input(), &Default::default()).unwrap(),
+            "CMZ"
+        );
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(
+            Day05::part2(&test_input(), &Default::default()).unwrap(),
+            "MCD"
+        );
+    }
+
+    #[test]
+    fn animation() {
+        for (part, name) in [(Part::One, "frames-9000"), (Part::Two, "frames-9001")] {
+            let animation = Day05::animation(&test_input(), part, &Default::default(), 0).unwrap();
+            assert_eq!(animation.frames.len(), 5);
+            common::assert_snapshot!(name, common::visual::ascii(&animation));
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Day05::parse("[A]\n [B]\n").unwrap_err(),
+            ParseError::new(2, 2, "crate is not aligned with a stack")
+        );
+        assert_eq!(
+            Day05::parse("[A] [B]\n\nmove 1 from 1 to 2\nmove 1 from 1 to 3").unwrap_err(),
+            ParseError::new(4, 18, "there is no stack 3, the ship has 2")
+        );
+        assert_eq!(
+            Day05::parse("[A] [B]\n\nmove 1 from 1 into 2").unwrap_err(),
+            ParseError::new(3, 15, "expected `to`, found `into`")
+        );
+        assert_eq!(
+            Day05::parse("[A] [B]\n\nmove x from 1 to 2").unwrap_err(),
+            ParseError::new(3, 6, "expected a number after `move`, found `x`")
+        );
+        assert_eq!(
+            Day05::parse("[A] [B]\n\nmove 1 from 1").unwrap_err(),
+            ParseError::new(3, 14, "expected `to`")
+        );
+        assert_eq!(
+            Day05::parse("[A] [B]\n\nmove 1 from 1 to 2\nmvoe 1 from 2 to 1").unwrap_err(),
+            ParseError::new(4, 1, "expected `move`, found `mvoe`")
+        );
+        assert_eq!(
+            Day05::parse("[A] [B]\n\nmove 1 from 1 to 2\n\ngarbage\n").unwrap_err(),
+            ParseError::new(5, 1, "expected `move`, found `garbage`")
+        );
+        assert_eq!(
+            Day05::parse("[A] zz\n\nmove 1 from 1 to 2").unwrap_err(),
+            ParseError::new(1, 5, "expected a crate like `[A]`, found `zz`")
+        );
+        assert_eq!(
+            Day05::parse("[A] [B]\n 1  x\n\nmove 1 from 1 to 2").unwrap_err(),
+            ParseError::new(2, 2, "expected a crate like `[A]`, found `1`")
+        );
+    }
+}
@@ -1,5 +1,9 @@
+use common::ParseError;
+#[cfg(test)]
 use std::collections::HashSet;
+use std::io::BufRead;
 
+/// The letters received by the device.
 pub struct Datastream {
     message: String,
 }
@@ -11,20 +15,92 @@ impl Datastream {
         }
     }
 
+    /// The datastream of a single line input.
+    ///
+    /// Windows of the message are sliced by byte,
+    /// so only ASCII letters are accepted.
+    pub fn from_input(input: impl BufRead) -> Result<Datastream, ParseError> {
+        let mut lines = common::lines(input);
+        let message = match lines.next() {
+            Some(line) => line?.1,
+            None => String::new(),
+        };
+
+        if let Some((column, chr)) = message
+            .chars()
+            .enumerate()
+            .find(|(_, chr)| !chr.is_ascii_alphabetic())
+        {
+            let message = format!("expected a letter, found `{chr}`");
+            return Err(ParseError::new(1, column + 1, message));
+        }
+
+        for line in lines {
+            let (number, line) = line?;
+            if !line.is_empty() {
+                return Err(ParseError::new(number, 1, "expected a single line"));
+            }
+        }
+
+        Ok(Datastream { message })
+    }
+
+    /// How many letters are received until the end of the first
+    /// start-of-packet marker, 4 different letters.
     pub fn first_packet(&self) -> Option<usize> {
-        let packet_group = 4;
-        let index = self.index_of_first_unique_chars(packet_group)?;
-        Some(index + packet_group)
+        self.first_marker(4)
     }
 
+    /// How many letters are received until the end of the first
+    /// start-of-message marker, 14 different letters.
     pub fn first_message(&self) -> Option<usize> {
-        let packet_group = 14;
-        let index = self.index_of_first_unique_chars(packet_group)?;
-        Some(index + packet_group)
+        self.first_marker(14)
     }
 
+    /// How many letters are received until the end of the first
+    /// marker of `size` different letters.
+    pub fn first_marker(&self, size: usize) -> Option<usize> {
+        let index = self.index_of_first_unique_chars(size)?;
+        Some(index + size)
+    }
+
+    // Slides a window over the message, counting how many times each
+    // letter is in it and how many letters are in it more than once
     fn index_of_first_unique_chars(&self, amount: usize) -> Option<usize> {
-        for i in 0..(self.message.len() - amount + 1) {
+        if amount == 0 {
+            return Some(0);
+        }
+
+        let bytes = self.message.as_bytes();
+        let mut counts = [0usize; 256];
+        let mut repeated = 0;
+
+        for (i, &byte) in bytes.iter().enumerate() {
+            counts[byte as usize] += 1;
+            if counts[byte as usize] == 2 {
+                repeated += 1;
+            }
+
+            if i >= amount {
+                let left = bytes[i - amount] as usize;
+                counts[left] -= 1;
+                if counts[left] == 1 {
+                    repeated -= 1;
+                }
+            }
+
+            if i + 1 >= amount && repeated == 0 {
+                return Some(i + 1 - amount);
+            }
+        }
+
+        None
+    }
+
+    // Checks every window on its own, which is slow but obviously right
+    #[cfg(test)]
+    fn index_of_first_unique_chars_naive(&self, amount: usize) -> Option<usize> {
+        for i in 0..(self.message.len() + 1).checked_sub(amount)? {
             let slice = &self.message[i..i + amount];
 
             if Datastream::has_unique_chars(slice) {
@@ -35,13 +111,14 @@ impl Datastream {
         None
     }
 
+    #[cfg(test)]
     fn has_unique_chars(string: &str) -> bool {
         let mut has_unique_chars = true;
 
         let mut set = HashSet::new();
 
         for chr in string.chars() {
-            has_unique_chars = has_unique_chars & set.insert(chr);
+            has_unique_chars &= set.insert(chr);
         }
 
         has_unique_chars
@@ -51,6 +128,7 @@ impl Datastream {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn unique_chars() {
@@ -74,6 +152,29 @@ mod tests {
         assert_eq!(ds.first_packet(), Some(6));
     }
 
+    #[test]
+    fn too_short() {
+        let ds = Datastream::new("abc");
+        assert_eq!(ds.first_packet(), None);
+        assert_eq!(ds.first_message(), None);
+    }
+
+    #[test]
+    fn from_input() {
+        assert_eq!(
+            Datastream::from_input("abcd\n".as_bytes()).unwrap().message,
+            "abcd"
+        );
+        assert_eq!(
+            Datastream::from_input("abc1d\n".as_bytes()).err(),
+            Some(ParseError::new(1, 4, "expected a letter, found `1`"))
+        );
+        assert_eq!(
+            Datastream::from_input("abcd\n\nefgh\n".as_bytes()).err(),
+            Some(ParseError::new(3, 1, "expected a single line"))
+        );
+    }
+
     #[test]
     fn first_message() {
         assert_eq!(
@@ -83,4 +184,29 @@ mod tests {
             19
         );
     }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(2000))]
+
+        // Few letters, so that windows of unique ones are rare
+        #[test]
+        fn same_index_as_naive(message in "[a-f]{0,40}", amount in 0usize..8) {
+            let ds = Datastream::new(&message);
+            prop_assert_eq!(
+                ds.index_of_first_unique_chars(amount),
+                ds.index_of_first_unique_chars_naive(amount)
+            );
+        }
+
+        #[test]
+        fn same_markers_as_naive(message in "[a-z]{0,60}") {
+            let ds = Datastream::new(&message);
+            for amount in [4, 14] {
+                prop_assert_eq!(
+                    ds.index_of_first_unique_chars(amount),
+                    ds.index_of_first_unique_chars_naive(amount)
+                );
+            }
+        }
+    }
 }
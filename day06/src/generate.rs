@@ -0,0 +1,60 @@
+use common::{Generator, Rng};
+
+use crate::Day06;
+
+impl Day06 {
+    // Replaces the letters at `start` with `len` different ones
+    fn plant_marker(rng: &mut Rng, stream: &mut [char], start: usize, len: usize) {
+        let mut letters: Vec<char> = ('d'..='z').collect();
+        rng.shuffle(&mut letters);
+        stream[start..start + len].copy_from_slice(&letters[..len]);
+    }
+}
+
+impl Generator for Day06 {
+    const SIZE: usize = 4096;
+
+    // `size` is the length of the datastream, at least 18 letters. It is
+    // made of only three letters, so it has no marker until the ones
+    // planted in it: a packet marker first and a message marker after.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let len = size.max(18);
+        let mut stream: Vec<char> = (0..len).map(|_| *rng.pick(&['a', 'b', 'c'])).collect();
+
+        let packet = rng.index(len - 17);
+        let message = packet + 4 + rng.index(len - packet - 17);
+        Day06::plant_marker(rng, &mut stream, packet, 4);
+        Day06::plant_marker(rng, &mut stream, message, 14);
+
+        let mut input: String = stream.into_iter().collect();
+        input.push('\n');
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_are_valid() {
+        for seed in 0..20 {
+            let input = common::generate::<Day06>(seed as usize * 10, seed);
+            assert_eq!(input.trim_end().len(), (seed as usize * 10).max(18));
+
+            let datastream = Day06::parse(&input).unwrap();
+            let packet = Day06::part1(&datastream, &Default::default()).unwrap();
+            let message = Day06::part2(&datastream, &Default::default()).unwrap();
+            assert!(packet <= message - 10);
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(
+            common::generate::<Day06>(50, 1),
+            common::generate::<Day06>(50, 1)
+        );
+    }
+}
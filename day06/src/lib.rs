@@ -0,0 +1,145 @@
+//! Day 6: Tuning Trouble.
+//!
+//! The input is a single line of letters received by the device,
+//! where markers are the first runs of letters that are all different.
+//!
+//! ```
+//! use day06::ds::Datastream;
+//!
+//! let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n".as_bytes();
+//! let datastream = Datastream::from_input(input).unwrap();
+//! assert_eq!(datastream.first_packet(), Some(7));
+//! assert_eq!(datastream.first_message(), Some(19));
+//! ```
+
+use common::{ParseError, Solution};
+use serde::{Deserialize, Serialize};
+use std::io::BufRead;
+
+pub mod ds;
+mod generate;
+
+use ds::Datastream;
+
+/// Knobs of the puzzle, the puzzle's own values by default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Options {
+    /// Different letters in a start-of-packet marker.
+    pub packet_size: usize,
+    /// Different letters in a start-of-message marker.
+    pub message_size: usize,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            packet_size: 4,
+            message_size: 14,
+        }
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Datastream;
+    type Options = Options;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn read(input: impl BufRead) -> Result<Datastream, ParseError> {
+        Datastream::from_input(input)
+    }
+
+    fn part1(datastream: &Datastream, options: &Options) -> Option<usize> {
+        datastream.first_marker(options.packet_size)
+    }
+
+    fn part2(datastream: &Datastream, options: &Options) -> Option<usize> {
+        datastream.first_marker(options.message_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1() {
+        assert_eq!(
+            Datastream::new("mjqjpqmgbljsphdztnvjfqwrcgsmlb")
+                .first_packet()
+                .unwrap(),
+            7
+        );
+        assert_eq!(
+            Datastream::new("bvwbjplbgvbhsrlpgdmjqwftvncz")
+                .first_packet()
+                .unwrap(),
+            5
+        );
+        assert_eq!(
+            Datastream::new("nppdvjthqldpwncqszvftbrmjlhg")
+                .first_packet()
+                .unwrap(),
+            6
+        );
+        assert_eq!(
+            Datastream::new("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")
+                .first_packet()
+                .unwrap(),
+            10
+        );
+        assert_eq!(
+            Datastream::new("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")
+                .first_packet()
+                .unwrap(),
+            11
+        );
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(
+            Datastream::new("mjqjpqmgbljsphdztnvjfqwrcgsmlb")
+                .first_message()
+                .unwrap(),
+            19
+        );
+        assert_eq!(
+            Datastream::new("bvwbjplbgvbhsrlpgdmjqwftvncz")
+                .first_message()
+                .unwrap(),
+            23
+        );
+        assert_eq!(
+            Datastream::new("nppdvjthqldpwncqszvftbrmjlhg")
+                .first_message()
+                .unwrap(),
+            23
+        );
+        assert_eq!(
+            Datastream::new("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")
+                .first_message()
+                .unwrap(),
+            29
+        );
+        assert_eq!(
+            Datastream::new("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")
+                .first_message()
+                .unwrap(),
+            26
+        );
+    }
+    #[test]
+    fn marker_sizes() {
+        let datastream = Datastream::new("mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        let options = Options {
+            packet_size: 3,
+            message_size: 5,
+        };
+        assert_eq!(Day06::part1(&datastream, &options), Some(3));
+        assert_eq!(Day06::part2(&datastream, &options), Some(8));
+    }
+}
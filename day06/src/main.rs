@@ -1,90 +1,12 @@
-use std::fs;
+use std::fs::File;
+use std::io::BufReader;
 
-mod ds;
+use common::ParseError;
+use day06::Day06;
 
-use ds::Datastream;
+fn main() -> Result<(), ParseError> {
+    let input = File::open("data/input.txt").expect("Input file should exist at data/input.txt");
+    common::print_answers::<Day06>(BufReader::new(input))?;
 
-fn main() {
-    let s = fs::read_to_string("data/input.txt").unwrap();
-    println!(
-        "The start of the first packet ends at the {}th character.",
-        Datastream::new(&s).first_packet().unwrap()
-    );
-    println!(
-        "The start of the first message ends at the {}th character.",
-        Datastream::new(&s).first_message().unwrap()
-    );
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn part1() {
-        assert_eq!(
-            Datastream::new("mjqjpqmgbljsphdztnvjfqwrcgsmlb")
-                .first_packet()
-                .unwrap(),
-            7
-        );
-        assert_eq!(
-            Datastream::new("bvwbjplbgvbhsrlpgdmjqwftvncz")
-                .first_packet()
-                .unwrap(),
-            5
-        );
-        assert_eq!(
-            Datastream::new("nppdvjthqldpwncqszvftbrmjlhg")
-                .first_packet()
-                .unwrap(),
-            6
-        );
-        assert_eq!(
-            Datastream::new("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")
-                .first_packet()
-                .unwrap(),
-            10
-        );
-        assert_eq!(
-            Datastream::new("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")
-                .first_packet()
-                .unwrap(),
-            11
-        );
-    }
-
-    #[test]
-    fn part2() {
-        assert_eq!(
-            Datastream::new("mjqjpqmgbljsphdztnvjfqwrcgsmlb")
-                .first_message()
-                .unwrap(),
-            19
-        );
-        assert_eq!(
-            Datastream::new("bvwbjplbgvbhsrlpgdmjqwftvncz")
-                .first_message()
-                .unwrap(),
-            23
-        );
-        assert_eq!(
-            Datastream::new("nppdvjthqldpwncqszvftbrmjlhg")
-                .first_message()
-                .unwrap(),
-            23
-        );
-        assert_eq!(
-            Datastream::new("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")
-                .first_message()
-                .unwrap(),
-            29
-        );
-        assert_eq!(
-            Datastream::new("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")
-                .first_message()
-                .unwrap(),
-            26
-        );
-    }
+    Ok(())
 }
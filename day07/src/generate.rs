@@ -0,0 +1,132 @@
+use common::{Generator, Rng};
+
+use crate::Day07;
+
+#[derive(Default)]
+struct Dir {
+    name: String,
+    dirs: Vec<usize>,
+    // Names and sizes
+    files: Vec<(String, u64)>,
+}
+
+impl Day07 {
+    fn name(rng: &mut Rng) -> String {
+        let letters: Vec<char> = ('a'..='z').collect();
+        (0..rng.range(1..=8)).map(|_| *rng.pick(&letters)).collect()
+    }
+
+    // A name that none of the directory's entries has yet
+    fn unique_name(rng: &mut Rng, dirs: &[Dir], dir: &Dir) -> String {
+        loop {
+            let name = Day07::name(rng);
+            let taken = dir.dirs.iter().any(|&child| dirs[child].name == name)
+                || dir.files.iter().any(|(file, _)| *file == name);
+            if !taken {
+                return name;
+            }
+        }
+    }
+
+    // Goes into `index`, lists it, then does the same for each directory in it
+    fn session(rng: &mut Rng, dirs: &[Dir], index: usize, input: &mut String) {
+        let dir = &dirs[index];
+        *input += &format!("$ cd {}\n$ ls\n", dir.name);
+
+        let mut entries: Vec<String> = dir
+            .dirs
+            .iter()
+            .map(|&child| format!("dir {}", dirs[child].name))
+            .chain(
+                dir.files
+                    .iter()
+                    .map(|(name, size)| format!("{size} {name}")),
+            )
+            .collect();
+        rng.shuffle(&mut entries);
+        for entry in entries {
+            *input += &entry;
+            input.push('\n');
+        }
+
+        for &child in &dir.dirs {
+            Day07::session(rng, dirs, child, input);
+            *input += "$ cd ..\n";
+        }
+    }
+}
+
+impl Generator for Day07 {
+    const SIZE: usize = 200;
+
+    // `size` is the number of directories besides the root, each in a
+    // random one made before it. Files add up to between 45M and 65M,
+    // so the disk is too full for the update but not overflowing.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut dirs = vec![Dir {
+            name: String::from("/"),
+            ..Dir::default()
+        }];
+
+        for index in 1..=size {
+            let parent = rng.index(index);
+            let name = Day07::unique_name(rng, &dirs, &dirs[parent]);
+            dirs.push(Dir {
+                name,
+                ..Dir::default()
+            });
+            dirs[parent].dirs.push(index);
+        }
+
+        // Files get a weight first, and their share of the total size after
+        let mut weights = Vec::new();
+        for index in 0..dirs.len() {
+            let min_files = if index == 0 { 1 } else { 0 };
+            for _ in 0..rng.range(min_files..=4) {
+                let mut name = Day07::unique_name(rng, &dirs, &dirs[index]);
+                if rng.chance(1, 2) {
+                    name += &format!(".{}", rng.pick(&["txt", "dat", "log", "lst"]));
+                }
+                dirs[index].files.push((name, 0));
+                weights.push(rng.range(1..=1000));
+            }
+        }
+
+        let total = rng.range(45_000_000..=65_000_000);
+        let weight_sum: u64 = weights.iter().sum();
+        let mut weights = weights.into_iter();
+        for dir in &mut dirs {
+            for (_, size) in &mut dir.files {
+                let weight = weights.next().expect("Every file has a weight");
+                *size = (total * weight / weight_sum).max(1);
+            }
+        }
+
+        let mut input = String::new();
+        Day07::session(rng, &dirs, 0, &mut input);
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_are_valid() {
+        for seed in 0..20 {
+            let input = common::generate::<Day07>(seed as usize * 5, seed);
+            let file_system = Day07::parse(&input).unwrap();
+            assert!(Day07::part2(&file_system, &Default::default()).is_some());
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(
+            common::generate::<Day07>(50, 1),
+            common::generate::<Day07>(50, 1)
+        );
+    }
+}
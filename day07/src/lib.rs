@@ -0,0 +1,324 @@
+//! Day 7: No Space Left On Device.
+//!
+//! The input is a terminal session going through directories with `cd`
+//! and listing their files with `ls`, from which the file system is rebuilt.
+//!
+//! ```
+//! use day07::simple_path::SimplePath;
+//! use day07::FileSystem;
+//!
+//! let session = "$ cd /\n$ ls\ndir a\n100 b.txt\n$ cd a\n$ ls\n50 c.txt";
+//! let file_system = FileSystem::from_commands(session.as_bytes()).unwrap();
+//! assert_eq!(file_system.dir_size(&SimplePath::new("/")), Some(150));
+//! assert_eq!(file_system.sum_of_sizes_below(100), 50);
+//!
+//! let (dir, size) = file_system.dir_to_delete(200, 90).unwrap();
+//! assert_eq!((dir.as_str(), size), ("/a", 50));
+//! ```
+
+use common::trace::Level;
+use common::{ParseError, Solution};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::BufRead;
+
+mod generate;
+pub mod simple_path;
+
+use simple_path::SimplePath;
+
+/// The directories seen in a terminal session, with the size of their files.
+#[derive(Debug)]
+pub struct FileSystem {
+    // u64 value is the folder's total file size, not counting subfolders
+    // for that, use the dir_size method
+    dirs: HashMap<SimplePath, u64>,
+    cwd: SimplePath,
+}
+
+impl FileSystem {
+    fn new() -> FileSystem {
+        FileSystem {
+            dirs: HashMap::new(),
+            cwd: SimplePath::new("/"),
+        }
+    }
+
+    /// Replays a terminal session, starting from the root directory.
+    pub fn from_commands(commands: impl BufRead) -> Result<FileSystem, ParseError> {
+        let mut fs = FileSystem::new();
+        // Lines that are not commands are only valid as the output of `ls`
+        let mut listing = false;
+
+        for line in common::lines(commands) {
+            let (number, line) = line?;
+            let fields: Vec<(usize, &str)> = common::fields(&line).collect();
+            let end = line.chars().count() + 1;
+
+            match fields.as_slice() {
+                [] => (),
+                [(_, "$"), (_, "cd"), (column, dir_name)] => {
+                    listing = false;
+
+                    if *dir_name == ".." {
+                        fs.cwd = fs.cwd.parent().ok_or_else(|| {
+                            ParseError::new(number, *column, "cannot go above the root directory")
+                        })?;
+                    } else {
+                        fs.cwd = fs.cwd.join(dir_name);
+                    }
+                    common::event!(Level::Debug, "cd", {
+                        "line": number,
+                        "to": dir_name,
+                        "cwd": fs.cwd.as_str(),
+                    });
+                }
+                [(_, "$"), (_, "ls")] => {
+                    listing = true;
+                    fs.dirs.insert(fs.cwd.clone(), 0);
+                    common::event!(Level::Debug, "ls", { "line": number, "cwd": fs.cwd.as_str() });
+                }
+                [(_, "$"), rest @ ..] => {
+                    let (column, command) = rest.first().copied().unwrap_or((end, ""));
+                    let message = format!("expected `cd <dir>` or `ls`, found `{command}`");
+                    return Err(ParseError::new(number, column, message));
+                }
+                [(column, _), ..] if !listing => {
+                    let message = "expected a command starting with `$`";
+                    return Err(ParseError::new(number, *column, message));
+                }
+                [(_, "dir"), (_, _)] => (),
+                [(column, size), (_, _)] => {
+                    let size: u64 = size.parse().map_err(|_| {
+                        let message = format!("expected a file size, found `{size}`");
+                        ParseError::new(number, *column, message)
+                    })?;
+
+                    *fs.dirs.entry(fs.cwd.clone()).or_default() += size;
+                }
+                [(column, _), ..] => {
+                    let message = "expected `dir <name>` or `<size> <name>`";
+                    return Err(ParseError::new(number, *column, message));
+                }
+            }
+        }
+
+        Ok(fs)
+    }
+}
+
+impl FileSystem {
+    /// Size of every file in the directory, and in the ones inside it.
+    pub fn dir_size(&self, path: &SimplePath) -> Option<u64> {
+        let mut size = 0;
+
+        for (other_path, other_size) in &self.dirs {
+            if path.contains(other_path) {
+                size += other_size;
+            }
+        }
+
+        Some(size)
+    }
+
+    /// Size of every directory, in no particular order.
+    pub fn dir_sizes(&self) -> Vec<u64> {
+        let mut dir_sizes: Vec<u64> = Vec::new();
+        for path in self.dirs.keys() {
+            if let Some(size) = self.dir_size(path) {
+                dir_sizes.push(size);
+            }
+        }
+        dir_sizes
+    }
+
+    /// Sum of the sizes of the directories of at most `max_size`.
+    pub fn sum_of_sizes_below(&self, max_size: u64) -> u64 {
+        let mut sum = 0;
+
+        for size in self.dir_sizes() {
+            if size <= max_size {
+                sum += size;
+            }
+        }
+
+        sum
+    }
+
+    // The smallest directory of at least `greater_than`, and its size.
+    // Directories of the same size are told apart by their path.
+    fn min_dir_gt(&self, greater_than: u64) -> Option<(&SimplePath, u64)> {
+        let mut min_dir: Option<(&SimplePath, u64)> = None;
+        for path in self.dirs.keys() {
+            let size = self.dir_size(path)?;
+            if size < greater_than {
+                continue;
+            }
+
+            let is_smaller = match min_dir {
+                None => true,
+                Some((min_path, min_size)) => (size, path.as_str()) < (min_size, min_path.as_str()),
+            };
+            if is_smaller {
+                min_dir = Some((path, size));
+            }
+        }
+
+        min_dir
+    }
+
+    /// The smallest directory to delete, and its size, to have
+    /// `needed_size` free on a disk of `total_size`. None when there
+    /// already is enough free space.
+    pub fn dir_to_delete(&self, total_size: u64, needed_size: u64) -> Option<(&SimplePath, u64)> {
+        let used_size = self.dir_size(&SimplePath::new("/"))?;
+
+        // A disk too small for the files has no free space left
+        let free_size = total_size.saturating_sub(used_size);
+
+        if free_size >= needed_size {
+            None
+        } else {
+            self.min_dir_gt(needed_size - free_size)
+        }
+    }
+
+    pub fn dir_size_to_delete(&self, total_size: u64, needed_size: u64) -> Option<u64> {
+        let (_, size) = self.dir_to_delete(total_size, needed_size)?;
+        Some(size)
+    }
+}
+
+/// Knobs of the puzzle, the puzzle's own values by default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Options {
+    /// Largest size of the directories added up in part 1.
+    pub max_size: u64,
+    /// Size of the whole disk.
+    pub total_size: u64,
+    /// Free space the update needs.
+    pub needed_size: u64,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            max_size: 100_000,
+            total_size: 70_000_000,
+            needed_size: 30_000_000,
+        }
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = FileSystem;
+    type Options = Options;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn read(input: impl BufRead) -> Result<FileSystem, ParseError> {
+        FileSystem::from_commands(input)
+    }
+
+    fn part1(file_system: &FileSystem, options: &Options) -> Option<u64> {
+        Some(file_system.sum_of_sizes_below(options.max_size))
+    }
+
+    fn part2(file_system: &FileSystem, options: &Options) -> Option<u64> {
+        file_system.dir_size_to_delete(options.total_size, options.needed_size)
+    }
+
+    fn details(file_system: &FileSystem, options: &Options) -> Option<Value> {
+        let (path, size) = file_system.dir_to_delete(options.total_size, options.needed_size)?;
+        Some(json!({ "deleted_dir": { "path": path.as_str(), "size": size } }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn dir_size() {
+        let string = fs::read_to_string("data/test-input.txt").unwrap();
+        let file_system = FileSystem::from_commands(string.as_bytes()).unwrap();
+
+        assert_eq!(file_system.dir_size(&SimplePath::new("/a/e")), Some(584));
+        assert_eq!(file_system.dir_size(&SimplePath::new("/a")), Some(94853));
+        assert_eq!(file_system.dir_size(&SimplePath::new("/d")), Some(24933642));
+        assert_eq!(file_system.dir_size(&SimplePath::new("/")), Some(48381165));
+    }
+
+    #[test]
+    fn part1() {
+        let string = fs::read_to_string("data/test-input.txt").unwrap();
+        let file_system = FileSystem::from_commands(string.as_bytes()).unwrap();
+
+        assert_eq!(file_system.sum_of_sizes_below(100_000), 95437);
+    }
+
+    #[test]
+    fn part2() {
+        let string = fs::read_to_string("data/test-input.txt").unwrap();
+        let file_system = FileSystem::from_commands(string.as_bytes()).unwrap();
+
+        assert_eq!(
+            file_system.dir_size_to_delete(70000000, 30000000),
+            Some(24933642)
+        );
+    }
+
+    #[test]
+    fn details() {
+        let string = fs::read_to_string("data/test-input.txt").unwrap();
+        let file_system = FileSystem::from_commands(string.as_bytes()).unwrap();
+
+        assert_eq!(
+            Day07::details(&file_system, &Default::default()),
+            Some(json!({ "deleted_dir": { "path": "/d", "size": 24933642 } }))
+        );
+    }
+
+    #[test]
+    fn options() {
+        let string = fs::read_to_string("data/test-input.txt").unwrap();
+        let file_system = FileSystem::from_commands(string.as_bytes()).unwrap();
+        let options = Options {
+            max_size: 1000,
+            total_size: 50_000_000,
+            ..Options::default()
+        };
+
+        assert_eq!(Day07::part1(&file_system, &options), Some(584));
+        assert_eq!(Day07::part2(&file_system, &options), Some(48381165));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            FileSystem::from_commands("$ cd /\n$ cd ..".as_bytes()).unwrap_err(),
+            ParseError::new(2, 6, "cannot go above the root directory")
+        );
+        assert_eq!(
+            FileSystem::from_commands("$ cd /\n$ rm -rf a".as_bytes()).unwrap_err(),
+            ParseError::new(2, 3, "expected `cd <dir>` or `ls`, found `rm`")
+        );
+        assert_eq!(
+            FileSystem::from_commands("$ cd /\n123 a.txt".as_bytes()).unwrap_err(),
+            ParseError::new(2, 1, "expected a command starting with `$`")
+        );
+        assert_eq!(
+            FileSystem::from_commands("$ ls\ndir a\n12k b.txt".as_bytes()).unwrap_err(),
+            ParseError::new(3, 1, "expected a file size, found `12k`")
+        );
+        assert_eq!(
+            FileSystem::from_commands("$ ls\n12 b c.txt".as_bytes()).unwrap_err(),
+            ParseError::new(2, 1, "expected `dir <name>` or `<size> <name>`")
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+/// An absolute path to a directory, like `/a/e`.
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct SimplePath {
     path: String,
@@ -12,21 +13,27 @@ impl SimplePath {
         }
     }
 
+    /// The path of `path` inside this one, or `path` itself when absolute.
     pub fn join(&self, path: &str) -> SimplePath {
         let p = Path::new(&self.path);
         SimplePath::new(p.join(path).as_path().to_str().unwrap())
     }
 
+    /// None for the root directory.
     pub fn parent(&self) -> Option<SimplePath> {
         let p = Path::new(&self.path);
-        match p.parent() {
-            Some(path) => Some(SimplePath::new(path.to_str().unwrap())),
-            None => None,
-        }
+        p.parent()
+            .map(|path| SimplePath::new(path.to_str().unwrap()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.path
     }
 
+    /// Whether `other` is this directory or inside of it.
     pub fn contains(&self, other: &Self) -> bool {
-        other.path.starts_with(&self.path)
+        // Compared by component, so `/a` does not contain `/ab`
+        Path::new(&other.path).starts_with(&self.path)
     }
 }
 
@@ -76,6 +83,9 @@ mod tests {
         let not_child = SimplePath::new("/z/a/b/c/f");
 
         assert!(parent.contains(&child));
+        assert!(parent.contains(&parent));
         assert!(!parent.contains(&not_child));
+        assert!(!parent.contains(&SimplePath::new("/a/b/cd")));
+        assert!(SimplePath::new("/").contains(&not_child));
     }
 }
@@ -0,0 +1,46 @@
+use common::{Generator, Rng};
+
+use crate::Day08;
+
+impl Generator for Day08 {
+    const SIZE: usize = 99;
+
+    // `size` is the width and height of the square grid, at least 1
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+
+        for _ in 0..size.max(1) {
+            for _ in 0..size.max(1) {
+                input += &rng.range(0..=9).to_string();
+            }
+            input.push('\n');
+        }
+
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_are_valid() {
+        for seed in 0..10 {
+            let input = common::generate::<Day08>(seed as usize, seed);
+            let grid = Day08::parse(&input).unwrap();
+            let visible = Day08::part1(&grid, &Default::default()).unwrap();
+            assert!(visible >= (seed * 4).saturating_sub(4).max(1));
+            assert!(Day08::part2(&grid, &Default::default()).is_some());
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(
+            common::generate::<Day08>(20, 1),
+            common::generate::<Day08>(20, 1)
+        );
+    }
+}
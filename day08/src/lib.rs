@@ -0,0 +1,57 @@
+//! Day 8: Treetop Tree House.
+//!
+//! The input is a grid of tree heights, from 0 to 9.
+//!
+//! ```
+//! use day08::tree::TreeGrid;
+//!
+//! let grid = TreeGrid::from_input("303\n255\n653".as_bytes()).unwrap();
+//! assert_eq!((grid.width(), grid.height()), (3, 3));
+//! assert_eq!(grid.tree_height(1, 1), Some(5));
+//! assert_eq!(grid.visible_trees(), Some(9));
+//! assert_eq!(grid.max_scenic_score(), Some(1));
+//! ```
+
+use common::{Animation, NoOptions, ParseError, Part, Solution};
+use std::io::BufRead;
+use std::time::Duration;
+
+mod generate;
+pub mod tree;
+
+use tree::TreeGrid;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = TreeGrid;
+    type Options = NoOptions;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn read(input: impl BufRead) -> Result<TreeGrid, ParseError> {
+        TreeGrid::from_input(input)
+    }
+
+    fn part1(grid: &TreeGrid, _options: &NoOptions) -> Option<u64> {
+        grid.visible_trees()
+    }
+
+    fn part2(grid: &TreeGrid, _options: &NoOptions) -> Option<u64> {
+        grid.max_scenic_score()
+    }
+
+    // At most 5 frames
+    fn animation(
+        grid: &TreeGrid,
+        part: Part,
+        _options: &NoOptions,
+        _max_frames: usize,
+    ) -> Option<Animation> {
+        let frames = match part {
+            Part::One => grid.visibility_frames(),
+            Part::Two => vec![grid.scenic_frame()],
+        };
+        Some(Animation::new(frames, Duration::from_secs(1)))
+    }
+}
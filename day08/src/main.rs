@@ -1,22 +1,12 @@
-use std::fs;
+use std::fs::File;
+use std::io::BufReader;
 
-mod tree;
+use common::ParseError;
+use day08::Day08;
 
-use std::io::Error;
-use tree::TreeGrid;
-
-fn main() -> Result<(), Error> {
-    let string = fs::read_to_string("data/input.txt")?;
-    let grid = TreeGrid::from_string(&string);
-    println!(
-        "The numbe of visible trees is: {}",
-        grid.visible_trees().unwrap()
-    );
-
-    println!(
-        "The highest scenic score possible is: {}",
-        grid.max_scenic_score().unwrap()
-    );
+fn main() -> Result<(), ParseError> {
+    let input = File::open("data/input.txt").expect("Input file should exist at data/input.txt");
+    common::print_answers::<Day08>(BufReader::new(input))?;
 
     Ok(())
 }
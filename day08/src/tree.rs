@@ -1,41 +1,188 @@
+use common::visual::{Cell, Color, Frame};
+use common::{Direction, Grid, ParseError};
+#[cfg(test)]
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::BufRead;
+
+type Point = common::Point<usize>;
 
+/// The heights of the trees of a rectangular grid.
 #[derive(Debug)]
 pub struct TreeGrid {
-    trees: HashMap<Point, Tree>,
+    trees: Grid<Tree>,
 }
 
 impl TreeGrid {
-    fn new() -> TreeGrid {
-        TreeGrid {
-            trees: HashMap::new(),
+    /// A row of the grid per line, every row as wide as the first one.
+    pub fn from_input(input: impl BufRead) -> Result<TreeGrid, ParseError> {
+        let mut rows: Vec<Vec<Tree>> = Vec::new();
+
+        for line in common::lines(input) {
+            let (number, line) = line?;
+            let mut row = Vec::new();
+            for (x, num) in line.chars().enumerate() {
+                let height = num.to_digit(10).ok_or_else(|| {
+                    ParseError::new(
+                        number,
+                        x + 1,
+                        format!("expected a tree height, found `{num}`"),
+                    )
+                })?;
+                row.push(Tree {
+                    height: height as u8,
+                });
+            }
+
+            // Every row should be as wide as the first one
+            if let Some(width) = rows.first().map(Vec::len) {
+                if width != row.len() {
+                    let row_width = row.len();
+                    let message = format!("expected {width} trees in this row, found {row_width}");
+                    return Err(ParseError::new(number, row_width.min(width) + 1, message));
+                }
+            }
+            rows.push(row);
         }
+
+        let trees = Grid::from_rows(rows).expect("Rows were checked to be as wide");
+        Ok(TreeGrid { trees })
     }
 
-    pub fn from_string(string: &str) -> TreeGrid {
-        let mut grid = TreeGrid::new();
+    pub fn width(&self) -> usize {
+        self.trees.width()
+    }
 
-        for (y, line) in string.lines().enumerate() {
-            for (x, num) in line.chars().enumerate() {
-                grid.trees.insert(
-                    Point { x, y },
-                    Tree {
-                        height: num.to_string().parse().unwrap(),
-                    },
-                );
-            }
+    pub fn height(&self) -> usize {
+        self.trees.height()
+    }
+
+    /// Height of the tree in column `x` of row `y`, both starting at 0.
+    pub fn tree_height(&self, x: usize, y: usize) -> Option<u8> {
+        Some(self.trees.get(Point { x, y })?.height)
+    }
+
+    // Every row and column, looked at from both of its ends
+    fn lines_of_sight(&self) -> Vec<Vec<Point>> {
+        let mut lines = Vec::new();
+
+        for y in 0..self.height() {
+            let row: Vec<Point> = (0..self.width()).map(|x| Point { x, y }).collect();
+            lines.push(row.iter().rev().copied().collect());
+            lines.push(row);
+        }
+        for x in 0..self.width() {
+            let column: Vec<Point> = (0..self.height()).map(|y| Point { x, y }).collect();
+            lines.push(column.iter().rev().copied().collect());
+            lines.push(column);
         }
 
-        grid
+        lines
     }
 
+    /// How many trees can be seen from outside the grid.
+    //
+    // A tree is visible when it is taller than every tree before it
+    // in one of its lines of sight, so each line is only walked once
     pub fn visible_trees(&self) -> Option<u64> {
+        let mut visible = HashSet::new();
+
+        for line in self.lines_of_sight() {
+            let mut tallest = None;
+            for point in line {
+                let tree = &self.trees[point];
+                if tallest.is_none_or(|tallest| tree.height > tallest) {
+                    tallest = Some(tree.height);
+                    visible.insert(point);
+                }
+            }
+        }
+
+        Some(visible.len() as u64)
+    }
+
+    /// The trees, shaded by height, then the ones visible from the west,
+    /// east, north and south in gold, one side after the other. Visible
+    /// trees show their height as text, the others a dot.
+    pub fn visibility_frames(&self) -> Vec<Frame> {
+        let mut visible = HashSet::new();
+        let mut frames = vec![self.frame(&visible).with_caption("trees")];
+
+        // Lines of sight come in pairs, the row or column looked at from
+        // its end then from its start
+        let lines = self.lines_of_sight();
+        let (rows, columns) = lines.split_at(2 * self.height());
+        for (side, lines, first) in [
+            ("west", rows, 1),
+            ("east", rows, 0),
+            ("north", columns, 1),
+            ("south", columns, 0),
+        ] {
+            for line in lines.iter().skip(first).step_by(2) {
+                let mut tallest = None;
+                for &point in line {
+                    let tree = &self.trees[point];
+                    if tallest.is_none_or(|tallest| tree.height > tallest) {
+                        tallest = Some(tree.height);
+                        visible.insert(point);
+                    }
+                }
+            }
+            let caption = format!("{} visible from the {side}", visible.len());
+            frames.push(self.frame(&visible).with_caption(caption));
+        }
+
+        frames
+    }
+
+    fn frame(&self, visible: &HashSet<Point>) -> Frame {
+        let mut frame = Frame::new(self.width(), self.height());
+        for (point, tree) in self.trees.iter() {
+            let cell = if visible.contains(&point) {
+                let glyph = char::from_digit(tree.height as u32, 10).unwrap_or('#');
+                Cell::new(glyph, Color::GOLD)
+            } else {
+                let shade = Color::DIM.towards(Color::GREEN, tree.height as f64 / 9.0);
+                Cell::new('.', shade)
+            };
+            frame.set(point.x, point.y, cell);
+        }
+        frame
+    }
+
+    /// The trees shaded by their scenic score, from dim to green, the
+    /// best one in red with its height as text.
+    pub fn scenic_frame(&self) -> Frame {
+        let scores: Vec<(Point, u64)> = self
+            .trees
+            .points()
+            .map(|point| (point, self.scenic_score(&point).unwrap_or(0)))
+            .collect();
+        let best = scores.iter().max_by_key(|(_, score)| *score).copied();
+        let max = best.map_or(0, |(_, score)| score).max(1);
+
+        let mut frame = Frame::new(self.width(), self.height());
+        for &(point, score) in &scores {
+            let shade = Color::DIM.towards(Color::GREEN, score as f64 / max as f64);
+            frame.set(point.x, point.y, Cell::new('.', shade));
+        }
+        if let Some((point, score)) = best {
+            let height = self.trees[point].height as u32;
+            let glyph = char::from_digit(height, 10).unwrap_or('#');
+            frame.set(point.x, point.y, Cell::new(glyph, Color::RED));
+            frame.caption = format!("best scenic score: {score}");
+        }
+        frame
+    }
+
+    // Checks every tree on its own, which is slow but obviously right
+    #[cfg(test)]
+    fn visible_trees_naive(&self) -> Option<u64> {
         let mut visible = 0;
 
-        for point in self.trees.keys() {
+        for point in self.trees.points() {
             if self
-                .is_visible(point)
+                .is_visible(&point)
                 .expect("Tree from Grid has a visibility.")
             {
                 visible += 1;
@@ -45,18 +192,16 @@ impl TreeGrid {
         Some(visible)
     }
 
+    #[cfg(test)]
     fn is_visible(&self, point: &Point) -> Option<bool> {
-        let tree = self.trees.get(point)?;
+        let tree = self.trees.get(*point)?;
 
         let mut is_visible = false;
 
         for point_list in self.maybe_blocking(point) {
             let mut is_visible_in_this_line = true;
             for other_point in point_list {
-                let other_tree = self
-                    .trees
-                    .get(&other_point)
-                    .expect("Point obtained from custom method");
+                let other_tree = &self.trees[other_point];
                 if other_tree.height >= tree.height {
                     is_visible_in_this_line = false;
                 }
@@ -67,10 +212,11 @@ impl TreeGrid {
         Some(is_visible)
     }
 
+    #[cfg(test)]
     fn maybe_blocking(&self, point: &Point) -> [Vec<Point>; 4] {
         let mut tree_lists: [Vec<Point>; 4] = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
 
-        for other_point in self.trees.keys() {
+        for other_point in self.trees.points() {
             let index = match (other_point.x.cmp(&point.x), other_point.y.cmp(&point.y)) {
                 (Ordering::Equal, Ordering::Less) => Some(0),
                 (Ordering::Equal, Ordering::Greater) => Some(1),
@@ -80,45 +226,57 @@ impl TreeGrid {
             };
 
             if let Some(index) = index {
-                tree_lists[index].push(*other_point);
+                tree_lists[index].push(other_point);
             }
         }
 
         tree_lists
     }
 
+    /// The best scenic score of the grid's trees, None for an empty grid.
     pub fn max_scenic_score(&self) -> Option<u64> {
-        if self.trees.is_empty() {
-            return None;
-        }
-        let mut max = 0;
+        self.trees
+            .points()
+            .map(|point| {
+                self.scenic_score(&point)
+                    .expect("This point should have a tree")
+            })
+            .max()
+    }
 
-        for point in self.trees.keys() {
-            let score = self
-                .scenic_score(point)
-                .expect("This point should have a tree");
+    // Walks away from the tree in each direction, until a tree
+    // at least as tall or the edge of the grid
+    fn scenic_score(&self, point: &Point) -> Option<u64> {
+        let tree = self.trees.get(*point)?;
+        let mut score = 1;
 
-            if max < score {
-                max = score;
+        for direction in Direction::ALL {
+            let mut line_score = 0;
+            for (_, other_tree) in self.trees.ray(*point, direction) {
+                line_score += 1;
+                if other_tree.height >= tree.height {
+                    break;
+                }
             }
+            score *= line_score;
         }
 
-        Some(max)
+        Some(score)
     }
 
-    fn scenic_score(&self, point: &Point) -> Option<u64> {
-        let tree = self.trees.get(point)?;
+    // Sorts every tree in line with this one by distance,
+    // which is slow but obviously right
+    #[cfg(test)]
+    fn scenic_score_naive(&self, point: &Point) -> Option<u64> {
+        let tree = self.trees.get(*point)?;
         let mut score = 1;
 
         for mut point_list in self.maybe_blocking(point) {
             let mut line_score = 0;
 
-            point_list.sort_by_key(|p| p.distance(point));
+            point_list.sort_by_key(|p| p.manhattan(point));
             for other_point in point_list {
-                let other_tree = self
-                    .trees
-                    .get(&other_point)
-                    .expect("Point obtained from custom method");
+                let other_tree = &self.trees[other_point];
 
                 line_score += 1;
                 if other_tree.height >= tree.height {
@@ -132,19 +290,6 @@ impl TreeGrid {
     }
 }
 
-#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
-struct Point {
-    x: usize,
-    y: usize,
-}
-
-impl Point {
-    fn distance(&self, other: &Point) -> u64 {
-        (((other.x.abs_diff(self.x)) as f64).hypot((other.y.abs_diff(self.y)) as f64) * 1000.0)
-            .round() as u64
-    }
-}
-
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 struct Tree {
     height: u8,
@@ -153,9 +298,10 @@ struct Tree {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     fn test_grid() -> TreeGrid {
-        TreeGrid::from_string("30373\n25512\n65332\n33549\n35390")
+        TreeGrid::from_input("30373\n25512\n65332\n33549\n35390".as_bytes()).unwrap()
     }
 
     fn test_grid_border_points() -> Vec<Point> {
@@ -176,11 +322,23 @@ mod tests {
     }
 
     #[test]
-    fn from_string() {
+    fn from_input() {
         let grid = test_grid();
         assert_eq!(grid.trees.len(), 25);
     }
 
+    #[test]
+    fn from_input_errors() {
+        assert_eq!(
+            TreeGrid::from_input("303\n2a5".as_bytes()).unwrap_err(),
+            ParseError::new(2, 2, "expected a tree height, found `a`")
+        );
+        assert_eq!(
+            TreeGrid::from_input("303\n25\n653".as_bytes()).unwrap_err(),
+            ParseError::new(2, 3, "expected 3 trees in this row, found 2")
+        );
+    }
+
     #[test]
     fn is_visible() {
         let grid = test_grid();
@@ -222,9 +380,53 @@ mod tests {
         assert_eq!(grid.scenic_score(&Point { x: 2, y: 3 }).unwrap(), 8);
     }
 
+    #[test]
+    fn frames() {
+        let grid = test_grid();
+        let frames = grid.visibility_frames();
+        assert_eq!(frames.len(), 5);
+        assert_eq!(frames[4].caption, "21 visible from the south");
+        assert_eq!(frames[4].text(), "30373\n255.2\n65.32\n3.5.9\n35390\n");
+
+        let scenic = grid.scenic_frame();
+        assert_eq!(scenic.caption, "best scenic score: 8");
+        assert_eq!(scenic.text(), ".....\n.....\n.....\n..5..\n.....\n");
+    }
+
     #[test]
     fn max_scenic_score() {
         let grid = test_grid();
         assert_eq!(grid.max_scenic_score().unwrap(), 8);
     }
+
+    // Rows of digits, all as wide as the first one
+    fn grid() -> impl Strategy<Value = TreeGrid> {
+        (1usize..8, 1usize..8)
+            .prop_flat_map(|(width, height)| {
+                prop::collection::vec(prop::collection::vec(0u8..10, width), height)
+            })
+            .prop_map(|rows| {
+                let lines: Vec<String> = rows
+                    .iter()
+                    .map(|row| row.iter().map(|height| height.to_string()).collect())
+                    .collect();
+                TreeGrid::from_input(lines.join("\n").as_bytes()).unwrap()
+            })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(1000))]
+
+        #[test]
+        fn same_visible_trees_as_naive(grid in grid()) {
+            prop_assert_eq!(grid.visible_trees(), grid.visible_trees_naive());
+        }
+
+        #[test]
+        fn same_scenic_scores_as_naive(grid in grid()) {
+            for point in grid.trees.points() {
+                prop_assert_eq!(grid.scenic_score(&point), grid.scenic_score_naive(&point));
+            }
+        }
+    }
 }
@@ -0,0 +1,44 @@
+use common::{Generator, Rng};
+
+use crate::Day09;
+
+impl Generator for Day09 {
+    const SIZE: usize = 2000;
+
+    // `size` is the number of lines, each moving the head 1 to 20 steps
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+
+        for _ in 0..size {
+            let direction = rng.pick(&['U', 'D', 'L', 'R']);
+            input += &format!("{direction} {}\n", rng.range(1..=20));
+        }
+
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_are_valid() {
+        for seed in 0..20 {
+            let input = common::generate::<Day09>(seed as usize * 10, seed);
+            let motions = Day09::parse(&input).unwrap();
+            let part1 = Day09::part1(&motions, &Default::default()).unwrap();
+            let part2 = Day09::part2(&motions, &Default::default()).unwrap();
+            assert!(part2 <= part1);
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(
+            common::generate::<Day09>(50, 1),
+            common::generate::<Day09>(50, 1)
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! Day 9: Rope Bridge.
+//!
+//! The input lists the moves of the head of a rope,
+//! whose knots each follow the one before them.
+//!
+//! ```
+//! use day09::rope::{Motion, RopeHistory, TooFewKnots};
+//!
+//! let motions = Motion::from_list("R 4\nU 4".as_bytes()).unwrap();
+//! let history = RopeHistory::from_motions_with(&motions, 2).unwrap();
+//! assert_eq!(history.tail_unique_positions(), 7);
+//!
+//! assert_eq!(RopeHistory::from_motions_with(&motions, 1).unwrap_err(), TooFewKnots);
+//! ```
+
+use common::visual::Sampling;
+use common::{Animation, ParseError, Part, Solution};
+use serde::{Deserialize, Serialize};
+use std::io::BufRead;
+use std::time::Duration;
+
+mod generate;
+pub mod rope;
+
+use rope::{Motion, RopeHistory};
+
+/// Knobs of the puzzle, the puzzle's own values by default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Options {
+    /// Knots of the longer rope of part 2, its head included.
+    pub knot_count: u8,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options { knot_count: 10 }
+    }
+}
+
+pub struct Day09;
+
+impl Day09 {
+    fn tail_unique_positions(motions: &[Motion], knot_count: u8) -> Option<usize> {
+        let history = RopeHistory::from_motions_with(motions, knot_count).ok()?;
+        Some(history.tail_unique_positions())
+    }
+}
+
+impl Solution for Day09 {
+    type Input = Vec<Motion>;
+    type Options = Options;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn read(input: impl BufRead) -> Result<Vec<Motion>, ParseError> {
+        Motion::from_list(input)
+    }
+
+    fn part1(motions: &Vec<Motion>, _options: &Options) -> Option<usize> {
+        Day09::tail_unique_positions(motions, 2)
+    }
+
+    fn part2(motions: &Vec<Motion>, options: &Options) -> Option<usize> {
+        Day09::tail_unique_positions(motions, options.knot_count)
+    }
+
+    fn animation(
+        motions: &Vec<Motion>,
+        part: Part,
+        options: &Options,
+        max_frames: usize,
+    ) -> Option<Animation> {
+        let knot_count = match part {
+            Part::One => 2,
+            Part::Two => options.knot_count,
+        };
+        let history = RopeHistory::from_motions_with(motions, knot_count).ok()?;
+        let delay =
+            Sampling::new(history.step_count(), max_frames).delay(Duration::from_millis(50));
+        Some(Animation::new(history.frames(max_frames), delay))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn knot_count() {
+        let motions = Day09::parse("R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n").unwrap();
+        let options = Options { knot_count: 2 };
+        assert_eq!(Day09::part2(&motions, &options), Some(13));
+        assert_eq!(Day09::part2(&motions, &Options::default()), Some(1));
+    }
+}
@@ -1,29 +1,12 @@
-use std::fs;
-use std::io::Error;
+use std::fs::File;
+use std::io::BufReader;
 
-mod rope;
+use common::ParseError;
+use day09::Day09;
 
-use rope::RopeHistory;
-
-fn main() -> Result<(), Error> {
-    let string = fs::read_to_string("data/input.txt")?;
-    let history = RopeHistory::from_input(&string);
-    println!(
-        "The tail visited, at least once, {} positions",
-        history.tail_unique_positions()
-    );
-
-    assert_eq!(history.tail_unique_positions(), 6023);
-
-    let knot_count = 10;
-    let history = RopeHistory::from_input_with(&string, knot_count).unwrap();
-    println!(
-        "With {} knots, however, the tail visited, at least once, {} positions",
-        knot_count,
-        history.tail_unique_positions()
-    );
-
-    assert_eq!(history.tail_unique_positions(), 2533);
+fn main() -> Result<(), ParseError> {
+    let input = File::open("data/input.txt").expect("Input file should exist at data/input.txt");
+    common::print_answers::<Day09>(BufReader::new(input))?;
 
     Ok(())
 }
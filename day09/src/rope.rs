@@ -1,29 +1,15 @@
-use regex::Regex;
+use common::trace::Level;
+use common::visual::{Cell, Color, Frame, Sampling};
+use common::ParseError;
 use std::collections::HashSet;
+use std::io::BufRead;
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn move_closer_to(&mut self, other: &Point) {
-        self.x += (other.x - self.x).signum();
-        self.y += (other.y - self.y).signum();
-    }
-
-    fn is_touching_with(&self, other: &Point) -> bool {
-        (self.x.abs_diff(other.x) <= 1) && (self.y.abs_diff(other.y) <= 1)
-    }
-}
-
-#[derive(Debug)]
-struct RopeMovementError;
+type Point = common::Point<i32>;
 
+/// A single step of the rope's head.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum RopeMovement {
+pub enum RopeMovement {
     Up,
     Down,
     Right,
@@ -31,7 +17,7 @@ enum RopeMovement {
 }
 
 impl FromStr for RopeMovement {
-    type Err = RopeMovementError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -39,29 +25,65 @@ impl FromStr for RopeMovement {
             "D" => Ok(RopeMovement::Down),
             "L" => Ok(RopeMovement::Left),
             "R" => Ok(RopeMovement::Right),
-            _ => Err(RopeMovementError),
+            _ => Err(ParseError::new(
+                1,
+                1,
+                format!("expected U, D, L or R, found `{s}`"),
+            )),
         }
     }
 }
 
 impl RopeMovement {
-    fn from_list(s: &str) -> Vec<RopeMovement> {
-        let re = Regex::new(r"(\w) (\d+)").unwrap();
-        let mut movements = Vec::new();
-
-        for caps in re.captures_iter(s) {
-            for _ in 0..caps.get(2).unwrap().as_str().parse::<usize>().unwrap() {
-                movements.push(
-                    caps.get(1)
-                        .unwrap()
-                        .as_str()
-                        .parse::<RopeMovement>()
-                        .unwrap(),
-                )
+    // Unlike on a grid, y grows going up
+    fn delta(&self) -> (isize, isize) {
+        match self {
+            RopeMovement::Up => (0, 1),
+            RopeMovement::Down => (0, -1),
+            RopeMovement::Right => (1, 0),
+            RopeMovement::Left => (-1, 0),
+        }
+    }
+}
+
+/// Steps of the rope's head in the same direction, a line of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Motion {
+    pub movement: RopeMovement,
+    pub steps: usize,
+}
+
+impl Motion {
+    /// Each line is a direction and how many steps to take in it. The
+    /// steps are only taken when the rope moves, however many there are.
+    pub fn from_list(input: impl BufRead) -> Result<Vec<Motion>, ParseError> {
+        let mut motions = Vec::new();
+
+        for line in common::lines(input) {
+            let (number, line) = line?;
+            let fields: Vec<(usize, &str)> = common::fields(&line).collect();
+
+            match fields.as_slice() {
+                [] => (),
+                [(direction_column, direction), (steps_column, steps)] => {
+                    let movement: RopeMovement = direction
+                        .parse()
+                        .map_err(|err: ParseError| err.relocate(number, *direction_column))?;
+                    let steps: usize = steps.parse().map_err(|_| {
+                        let message = format!("expected a number of steps, found `{steps}`");
+                        ParseError::new(number, *steps_column, message)
+                    })?;
+
+                    motions.push(Motion { movement, steps });
+                }
+                [(column, _), ..] => {
+                    let message = "expected a direction and a number of steps";
+                    return Err(ParseError::new(number, *column, message));
+                }
             }
         }
 
-        movements
+        Ok(motions)
     }
 }
 
@@ -71,15 +93,16 @@ struct Rope {
 }
 
 impl Rope {
+    #[cfg(test)]
     fn new() -> Rope {
         Rope {
             points: vec![Point { x: 0, y: 0 }, Point { x: 0, y: 0 }],
         }
     }
 
-    fn new_with(point_count: u8) -> Result<Rope, ()> {
+    fn new_with(point_count: u8) -> Result<Rope, TooFewKnots> {
         if point_count < 2 {
-            Err(())
+            Err(TooFewKnots)
         } else {
             let mut points = Vec::new();
             for _ in 0..point_count {
@@ -89,8 +112,9 @@ impl Rope {
         }
     }
 
+    #[cfg(test)]
     fn head(&self) -> &Point {
-        self.points.get(0).unwrap()
+        self.points.first().unwrap()
     }
 
     fn head_mut(&mut self) -> &mut Point {
@@ -107,133 +131,235 @@ impl Rope {
     }
 
     fn move_head(&mut self, movement: &RopeMovement) {
-        match movement {
-            RopeMovement::Up => self.head_mut().y += 1,
-            RopeMovement::Down => self.head_mut().y -= 1,
-            RopeMovement::Right => self.head_mut().x += 1,
-            RopeMovement::Left => self.head_mut().x -= 1,
-        }
+        let (dx, dy) = movement.delta();
+        let head = self.head_mut();
+        *head = head
+            .offset(dx, dy)
+            .expect("The rope stays far from i32::MAX");
     }
 
+    // Each knot follows the one before it when they are not touching,
+    // even diagonally, anymore
     fn move_nonhead(&mut self) {
         for i in 1..self.points.len() {
-            let range = &mut self.points[i - 1..i + 1];
-            let mut knot = range[1];
-            let previous = range[0];
+            let previous = self.points[i - 1];
+            let knot = &mut self.points[i];
 
-            if !knot.is_touching_with(&previous) {
-                knot.move_closer_to(&previous);
-                range[1] = knot;
+            if knot.chebyshev(&previous) > 1 {
+                *knot = knot.towards(&previous);
             }
         }
     }
 
+    #[cfg(test)]
     // part 1
     fn is_touching(&self) -> bool {
-        (self.head().x.abs_diff(self.tail().x) <= 1) && (self.head().y.abs_diff(self.tail().y) <= 1)
+        self.head().chebyshev(self.tail()) <= 1
     }
 }
 
+/// A rope needs at least a head and a tail.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TooFewKnots;
+
+/// Every position a rope goes through from its start, replayed from the
+/// motions whenever needed rather than kept.
 #[derive(Debug)]
-pub struct RopeHistory {
-    history: Vec<Rope>,
+pub struct RopeHistory<'a> {
+    motions: &'a [Motion],
+    knot_count: u8,
 }
 
-impl RopeHistory {
-    pub fn new() -> RopeHistory {
-        RopeHistory {
-            history: Vec::new(),
-        }
-    }
-
-    // part 1
-    pub fn from_input(s: &str) -> RopeHistory {
-        RopeHistory::from_input_with(s, 2).unwrap()
+impl<'a> RopeHistory<'a> {
+    /// Moves a rope of `knot_count` knots, all starting at the same place.
+    pub fn from_motions_with(
+        motions: &'a [Motion],
+        knot_count: u8,
+    ) -> Result<RopeHistory<'a>, TooFewKnots> {
+        Rope::new_with(knot_count)?;
+        Ok(RopeHistory {
+            motions,
+            knot_count,
+        })
     }
 
-    pub fn from_input_with(s: &str, knot_count: u8) -> Result<RopeHistory, ()> {
-        let mut rh = RopeHistory::new();
-        let mut rope = Rope::new_with(knot_count)?;
-        rh.history.push(rope.clone());
-
-        let movements = RopeMovement::from_list(s);
-
-        for movement in &movements {
-            rope.move_rope(movement);
-            rh.history.push(rope.clone());
+    // The rope at its start and after each step
+    fn replay(&self, mut visit: impl FnMut(usize, &Rope)) {
+        let mut rope = Rope::new_with(self.knot_count).expect("Checked when created");
+        visit(0, &rope);
+
+        let movements = self
+            .motions
+            .iter()
+            .flat_map(|motion| std::iter::repeat_n(motion.movement, motion.steps));
+        for (step, movement) in movements.enumerate() {
+            rope.move_rope(&movement);
+            common::event!(Level::Trace, "step", {
+                "step": step + 1,
+                "movement": format!("{movement:?}"),
+                "head": [rope.points[0].x, rope.points[0].y],
+                "tail": [rope.tail().x, rope.tail().y],
+            });
+            visit(step + 1, &rope);
         }
+    }
 
-        Ok(rh)
+    /// How many positions the rope goes through, the start included, one
+    /// frame each.
+    pub fn step_count(&self) -> usize {
+        self.motions
+            .iter()
+            .fold(1, |count, motion| count.saturating_add(motion.steps))
     }
 
+    /// How many different positions the tail goes through.
     pub fn tail_unique_positions(&self) -> usize {
         let mut tail_positions: HashSet<Point> = HashSet::new();
-
-        for rope in &self.history {
+        self.replay(|_, rope| {
             tail_positions.insert(*rope.tail());
-        }
-
+        });
         tail_positions.len()
     }
+
+    /// A frame per position of the rope, drawn like in the puzzle: the
+    /// head is `H`, the other knots are numbered from 1, or `T` when there
+    /// is only a tail, `s` is the start and `#` where the tail went.
+    ///
+    /// Only the `max_frames` positions a [`Sampling`] keeps are drawn, or
+    /// all of them for 0.
+    pub fn frames(&self, max_frames: usize) -> Vec<Frame> {
+        // Once to know how big the frames are, then again to draw them
+        let start = Point { x: 0, y: 0 };
+        let (mut min, mut max) = (start, start);
+        self.replay(|_, rope| {
+            for point in &rope.points {
+                (min.x, min.y) = (min.x.min(point.x), min.y.min(point.y));
+                (max.x, max.y) = (max.x.max(point.x), max.y.max(point.y));
+            }
+        });
+        let (width, height) = ((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize);
+        // Rows go down, unlike y
+        let cell = |point: &Point| ((point.x - min.x) as usize, (max.y - point.y) as usize);
+
+        let sampling = Sampling::new(self.step_count(), max_frames);
+        let mut visited = HashSet::new();
+        let mut frames = Vec::new();
+        self.replay(|step, rope| {
+            visited.insert(*rope.tail());
+            if !sampling.keeps(step) {
+                return;
+            }
+
+            let mut frame = Frame::new(width, height).with_caption(format!(
+                "step {step}, the tail went through {} positions",
+                visited.len()
+            ));
+            for point in &visited {
+                let (x, y) = cell(point);
+                frame.set(x, y, Cell::new('#', Color::DIM));
+            }
+            let (x, y) = cell(&start);
+            frame.set(x, y, Cell::new('s', Color::DIM));
+
+            // Knots closer to the head are drawn over the others
+            let knots = rope.points.len();
+            for (index, knot) in rope.points.iter().enumerate().rev() {
+                let glyph = match index {
+                    0 => 'H',
+                    1 if knots == 2 => 'T',
+                    _ => char::from_digit(index as u32, 36).unwrap_or('*'),
+                };
+                let color = match index {
+                    0 => Color::RED,
+                    _ if index == knots - 1 => Color::GREEN,
+                    _ => Color::GOLD,
+                };
+                let (x, y) = cell(knot);
+                frame.set(x, y, Cell::new(glyph, color));
+            }
+            frames.push(frame);
+        });
+
+        frames
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn test_movements() -> RopeHistory {
-        RopeHistory::from_input(
-            "R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2",
-        )
+    fn parse(input: &str) -> Vec<Motion> {
+        Motion::from_list(input.as_bytes()).unwrap()
     }
 
     #[test]
     fn tail_unique_positions() {
-        let history = test_movements();
+        let motions = parse("R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2");
+        let history = RopeHistory::from_motions_with(&motions, 2).unwrap();
         assert_eq!(history.tail_unique_positions(), 13);
     }
 
+    #[test]
+    fn long_motions() {
+        // A line per motion, however many steps it takes
+        let motions = parse("R 1000000\nU 2");
+        assert_eq!(motions.len(), 2);
+        let history = RopeHistory::from_motions_with(&motions, 2).unwrap();
+        assert_eq!(history.step_count(), 1_000_003);
+        assert_eq!(history.tail_unique_positions(), 1_000_001);
+    }
+
     #[test]
     fn part2() {
         let knot_count = 10;
-        let history = RopeHistory::from_input_with(
-            "R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2
-",
-            knot_count,
-        )
-        .unwrap();
+        let motions = parse("R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n");
+        let history = RopeHistory::from_motions_with(&motions, knot_count).unwrap();
         assert_eq!(history.tail_unique_positions(), 1);
 
-        let history = RopeHistory::from_input_with(
-            "R 5
-U 8
-L 8
-D 3
-R 17
-D 10
-L 25
-U 20
-",
-            knot_count,
-        )
-        .unwrap();
+        let motions = parse("R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20\n");
+        let history = RopeHistory::from_motions_with(&motions, knot_count).unwrap();
         assert_eq!(history.tail_unique_positions(), 36);
     }
 
+    #[test]
+    fn frames() {
+        let motions = parse("R 4\nU 2\nL 1");
+        let history = RopeHistory::from_motions_with(&motions, 2).unwrap();
+        let frames = history.frames(0);
+        assert_eq!(frames.len(), 8);
+        assert_eq!(frames[0].text(), "\n\nH\n");
+        assert_eq!(frames[4].text(), "\n\ns##TH\n");
+        assert_eq!(
+            frames[7].caption,
+            "step 7, the tail went through 5 positions"
+        );
+        assert_eq!(frames[7].text(), "   H\n    T\ns###\n");
+
+        let motions = parse("R 3");
+        let history = RopeHistory::from_motions_with(&motions, 10).unwrap();
+        assert_eq!(history.frames(0)[3].text(), "321H\n");
+    }
+
+    #[test]
+    fn sampled_frames() {
+        // Every frame is 20001 cells wide, too many to draw them all
+        let motions = parse("R 20000");
+        let history = RopeHistory::from_motions_with(&motions, 2).unwrap();
+        let frames = history.frames(5);
+        let captions: Vec<&str> = frames.iter().map(|frame| frame.caption.as_str()).collect();
+        assert_eq!(
+            captions,
+            [
+                "step 0, the tail went through 1 positions",
+                "step 5000, the tail went through 5000 positions",
+                "step 10000, the tail went through 10000 positions",
+                "step 15000, the tail went through 15000 positions",
+                "step 20000, the tail went through 20000 positions",
+            ]
+        );
+        assert_eq!(history.frames(1).len(), 1);
+    }
+
     #[test]
     fn move_head() {
         let mut rope = Rope::new();
@@ -317,6 +443,35 @@ U 20
         assert_eq!(rope.tail(), &Point { x: -2, y: 4 });
     }
 
+    #[test]
+    fn motion_from_list() {
+        assert_eq!(
+            parse("R 2\nU 1\n"),
+            [
+                Motion {
+                    movement: RopeMovement::Right,
+                    steps: 2
+                },
+                Motion {
+                    movement: RopeMovement::Up,
+                    steps: 1
+                }
+            ]
+        );
+        assert_eq!(
+            Motion::from_list("R 2\n  X 1".as_bytes()).unwrap_err(),
+            ParseError::new(2, 3, "expected U, D, L or R, found `X`")
+        );
+        assert_eq!(
+            Motion::from_list("R 2\nU -1".as_bytes()).unwrap_err(),
+            ParseError::new(2, 3, "expected a number of steps, found `-1`")
+        );
+        assert_eq!(
+            Motion::from_list("R 2\nU1".as_bytes()).unwrap_err(),
+            ParseError::new(2, 1, "expected a direction and a number of steps")
+        );
+    }
+
     #[test]
     fn rope_movement_from_str() {
         assert_eq!("U".parse::<RopeMovement>().unwrap(), RopeMovement::Up);
@@ -1,4 +1,7 @@
-use std::fs;
+use common::trace::Level;
+use common::visual::{Cell, Color, Frame, Sampling};
+use common::ParseError;
+use std::io::BufRead;
 use std::str::FromStr;
 
 enum Command {
@@ -7,20 +10,40 @@ enum Command {
 }
 
 impl FromStr for Command {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s == "noop" {
-            Ok(Command::Noop)
-        } else if s.contains("addx ") {
-            Ok(Command::Addx(s.split_whitespace().last().unwrap().parse::<i32>().unwrap()))
-        } else {
-            Err(())
+        let fields: Vec<(usize, &str)> = common::fields(s).collect();
+
+        match fields.as_slice() {
+            [(_, "noop")] => Ok(Command::Noop),
+            [(_, "addx"), (column, value)] => match value.parse::<i32>() {
+                Ok(value) => Ok(Command::Addx(value)),
+                Err(_) => Err(ParseError::new(
+                    1,
+                    *column,
+                    format!("expected a number to add, found `{value}`"),
+                )),
+            },
+            [(column, "addx")] => Err(ParseError::new(1, column + 4, "expected a number to add")),
+            [(_, "noop"), (column, field), ..] | [(_, "addx"), _, (column, field), ..] => {
+                Err(ParseError::new(
+                    1,
+                    *column,
+                    format!("expected the end of the line, found `{field}`"),
+                ))
+            }
+            [(column, command), ..] => Err(ParseError::new(
+                1,
+                *column,
+                format!("expected `noop` or `addx <value>`, found `{command}`"),
+            )),
+            [] => Err(ParseError::new(1, 1, "expected `noop` or `addx <value>`")),
         }
     }
-
 }
 
+/// The value of the X register during each cycle of a program.
 #[derive(Debug)]
 pub struct Program {
     x_values: Vec<i32>,
@@ -29,27 +52,39 @@ pub struct Program {
 }
 
 impl Program {
-    pub fn from_input(s: &str) -> Program {
+    /// Runs the program of the input, an instruction per line.
+    pub fn from_input(input: impl BufRead) -> Result<Program, ParseError> {
         let mut commands = Vec::new();
 
-        for line in s.lines() {
-            commands.push(line.parse().unwrap());
+        for line in common::lines(input) {
+            let (number, line) = line?;
+            let command = line
+                .parse()
+                .map_err(|err: ParseError| err.relocate(number, 1))?;
+            commands.push(command);
         }
 
-        Program::from_commands(&commands)
+        Ok(Program::from_commands(&commands))
     }
 
     fn from_commands(commands: &Vec<Command>) -> Program {
         let mut x = 1;
         let mut program = Program::new();
         for command in commands {
-            match command {
-                Command::Noop => program.x_values.push(x),
-                Command::Addx(value) => {
-                    program.x_values.push(x);
-                    program.x_values.push(x);
-                    x += value;
-                }
+            let (cycles, instruction) = match command {
+                Command::Noop => (1, String::from("noop")),
+                Command::Addx(value) => (2, format!("addx {value}")),
+            };
+            for _ in 0..cycles {
+                program.x_values.push(x);
+                common::event!(Level::Trace, "cycle", {
+                    "cycle": program.x_values.len(),
+                    "x": x,
+                    "instruction": instruction,
+                });
+            }
+            if let Command::Addx(value) = command {
+                x += value;
             }
         }
         program
@@ -63,43 +98,138 @@ impl Program {
         }
     }
 
+    /// Values of X during each cycle, the first one being cycle 1.
+    pub fn x_values(&self) -> &[i32] {
+        &self.x_values
+    }
+
+    /// Sum of the signal strengths during the 20th, 60th... 220th cycles,
+    /// or None when the program does not run that long.
     pub fn puzzle_sum(&self) -> Option<i32> {
+        self.signal_sum(&[20, 60, 100, 140, 180, 220])
+    }
+
+    /// Sum of the signal strengths during these cycles, the first one
+    /// being cycle 1, or None when the program does not run that long.
+    pub fn signal_sum(&self, cycles: &[usize]) -> Option<i32> {
         let mut sum = 0;
-        for i in [20, 60, 100, 140, 180, 220] {
+        for &i in cycles {
             sum += self.signal_strength(i)?;
         }
         Some(sum)
     }
 
     fn signal_strength(&self, cycle_num: usize) -> Option<i32> {
-        Some(self.x_values.get(cycle_num - 1)? * (cycle_num as i32))
+        Some(self.x_values.get(cycle_num.checked_sub(1)?)? * (cycle_num as i32))
     }
 
+    /// The CRT's screen, with `#` for lit pixels and `.` for dark ones.
     pub fn render(&self) -> String {
+        self.render_on(self.screen_size)
+    }
+
+    /// Like `render`, on a screen of another width and height.
+    pub fn render_on(&self, screen_size: (u16, u16)) -> String {
         let mut screen = String::new();
-        for cycle_num in 1..(self.x_values.len() + 1) {
-            self.draw(cycle_num, &mut screen).expect("Using indices from vec length");
+        for cycle_num in 1..(self.drawn_cycles(screen_size) + 1) {
+            self.draw(cycle_num, screen_size.0, &mut screen)
+                .expect("Using indices from vec length");
         }
         screen
     }
 
-    fn draw(&self, cycle_num: usize, screen: &mut String) -> Result<(), ()> {
-        if self.should_draw(cycle_num).ok_or(())? {
+    /// Which pixels are lit, row by row, for as many rows as the program draws.
+    pub fn pixels(&self) -> Vec<Vec<bool>> {
+        self.pixels_on(self.screen_size)
+    }
+
+    /// Like `pixels`, on a screen of another width and height.
+    pub fn pixels_on(&self, screen_size: (u16, u16)) -> Vec<Vec<bool>> {
+        let pixels: Vec<bool> = (1..(self.drawn_cycles(screen_size) + 1))
+            .map(|cycle_num| {
+                self.should_draw(cycle_num, screen_size.0)
+                    .expect("Using indices from vec length")
+            })
+            .collect();
+        pixels
+            .chunks(screen_size.0.max(1) as usize)
+            .map(<[bool]>::to_vec)
+            .collect()
+    }
+
+    /// A frame per cycle drawing a pixel on a screen of `screen_size`: the
+    /// pixels drawn so far, the one being drawn in red, and below the
+    /// screen, where the sprite is.
+    ///
+    /// Only the `max_frames` cycles a [`Sampling`] keeps are drawn, or all
+    /// of them for 0.
+    pub fn frames_on(&self, screen_size: (u16, u16), max_frames: usize) -> Vec<Frame> {
+        let (width, height) = (screen_size.0 as usize, screen_size.1 as usize);
+        let mut screen = Frame::new(width, height + 2);
+        let mut frames = Vec::new();
+        let sampling = Sampling::new(self.drawn_cycles(screen_size), max_frames);
+
+        for cycle_num in 1..(self.drawn_cycles(screen_size) + 1) {
+            let (x, y) = ((cycle_num - 1) % width, (cycle_num - 1) / width);
+            let lit = self
+                .should_draw(cycle_num, screen_size.0)
+                .expect("Using indices from vec length");
+            let sprite = *self
+                .sprite_hor_position(cycle_num)
+                .expect("Using indices from vec length");
+
+            let glyph = if lit { '#' } else { '.' };
+            if sampling.keeps(cycle_num - 1) {
+                let mut frame = screen.clone().with_caption(format!(
+                    "cycle {cycle_num}, X = {sprite}, signal strength {}",
+                    sprite * cycle_num as i32
+                ));
+                let half = (self.sprite_length / 2) as i32;
+                for column in (sprite - half..=sprite + half).filter(|&x| x >= 0) {
+                    frame.set(column as usize, height + 1, Cell::new('=', Color::GREEN));
+                }
+                frame.set(x, y, Cell::new(glyph, Color::RED));
+                frames.push(frame);
+            }
+
+            let color = if lit { Color::GOLD } else { Color::DIM };
+            screen.set(x, y, Cell::new(glyph, color));
+        }
+
+        frames
+    }
+
+    pub fn screen_width(&self) -> u16 {
+        self.screen_size.0
+    }
+
+    /// Cycles drawing a pixel on a screen of `screen_size`, the ones past
+    /// its last pixel drawing nothing.
+    pub fn drawn_cycles(&self, (width, height): (u16, u16)) -> usize {
+        self.x_values.len().min(width as usize * height as usize)
+    }
+
+    fn draw(&self, cycle_num: usize, width: u16, screen: &mut String) -> Result<(), ()> {
+        if self.should_draw(cycle_num, width).ok_or(())? {
             screen.push('#');
         } else {
             screen.push('.');
         }
 
-        if cycle_num % (self.screen_size.0 as usize) == 0 {
+        if cycle_num.is_multiple_of(width as usize) {
             screen.push('\n');
         }
 
         Ok(())
     }
 
-    fn should_draw(&self, cycle_num: usize) -> Option<bool> {
+    fn should_draw(&self, cycle_num: usize, width: u16) -> Option<bool> {
         let pixel_hor_index = (cycle_num as i32) - 1;
-        Some(self.sprite_hor_position(cycle_num)?.abs_diff(pixel_hor_index % self.screen_size.0 as i32) <= 1)
+        Some(
+            self.sprite_hor_position(cycle_num)?
+                .abs_diff(pixel_hor_index % width as i32)
+                <= (self.sprite_length / 2) as u32,
+        )
     }
 
     fn sprite_hor_position(&self, cycle_num: usize) -> Option<&i32> {
@@ -110,19 +240,40 @@ impl Program {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     fn simple_test_program() -> Program {
-        let commands = vec![
-            Command::Noop,
-            Command::Addx(3),
-            Command::Addx(-5),
-        ];
+        let commands = vec![Command::Noop, Command::Addx(3), Command::Addx(-5)];
         Program::from_commands(&commands)
     }
 
     fn big_test_program() -> Program {
         let s = fs::read_to_string("data/test-input.txt").unwrap();
-        Program::from_input(&s)
+        Program::from_input(s.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Program::from_input("noop\naddx 3\naddx\n".as_bytes()).unwrap_err(),
+            ParseError::new(3, 5, "expected a number to add")
+        );
+        assert_eq!(
+            Program::from_input("noop\naddx three".as_bytes()).unwrap_err(),
+            ParseError::new(2, 6, "expected a number to add, found `three`")
+        );
+        assert_eq!(
+            Program::from_input("noop\n  jmp 4".as_bytes()).unwrap_err(),
+            ParseError::new(2, 3, "expected `noop` or `addx <value>`, found `jmp`")
+        );
+        assert_eq!(
+            Program::from_input("noop 3".as_bytes()).unwrap_err(),
+            ParseError::new(1, 6, "expected the end of the line, found `3`")
+        );
+        assert_eq!(
+            Program::from_input("noop\naddx 1  2".as_bytes()).unwrap_err(),
+            ParseError::new(2, 9, "expected the end of the line, found `2`")
+        );
     }
 
     #[test]
@@ -137,47 +288,96 @@ mod tests {
         assert_eq!(program.signal_strength(6), None);
     }
 
+    #[test]
+    fn pixels() {
+        let pixels = big_test_program().pixels();
+        assert_eq!(pixels.len(), 6);
+        assert_eq!(
+            pixels[0][..8],
+            [true, true, false, false, true, true, false, false]
+        );
+
+        let rendered: Vec<String> = pixels
+            .iter()
+            .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+            .collect();
+        assert_eq!(rendered.join("\n") + "\n", big_test_program().render());
+    }
+
     #[test]
     fn part1() {
         let program = big_test_program();
         assert_eq!(program.puzzle_sum().unwrap(), 13140);
     }
 
+    #[test]
+    fn other_cycles() {
+        let program = big_test_program();
+        assert_eq!(program.signal_sum(&[20, 60]), Some(420 + 1140));
+        assert_eq!(program.signal_sum(&[0]), None);
+        assert_eq!(program.signal_sum(&[241]), None);
+    }
+
+    #[test]
+    fn other_screens() {
+        let program = big_test_program();
+        assert_eq!(program.render_on((8, 2)), "##..##..\n........\n");
+        assert_eq!(program.pixels_on((8, 2)).len(), 2);
+        assert_eq!(program.render_on((0, 6)), "");
+        assert!(program.pixels_on((0, 6)).is_empty());
+    }
+
+    #[test]
+    fn frames() {
+        let frames = big_test_program().frames_on((8, 2), 0);
+        assert_eq!(frames.len(), 16);
+        assert_eq!(frames[0].caption, "cycle 1, X = 1, signal strength 1");
+        assert_eq!(frames[0].text(), "#\n\n\n===\n");
+        assert_eq!(frames[9].text(), "##..##..\n..\n\n       =\n");
+        assert_eq!(frames[9].cells[common::Point::new(1, 1)].color, Color::RED);
+        assert!(simple_test_program().frames_on((0, 6), 0).is_empty());
+
+        let sampled = big_test_program().frames_on((8, 2), 4);
+        let captions: Vec<&str> = sampled.iter().map(|frame| frame.caption.as_str()).collect();
+        assert_eq!(
+            captions,
+            [
+                &frames[0].caption,
+                &frames[5].caption,
+                &frames[10].caption,
+                &frames[15].caption
+            ]
+        );
+        assert_eq!(sampled[3], frames[15]);
+    }
+
     #[test]
     fn should_draw() {
         let program = big_test_program();
-        assert!(program.should_draw(1).unwrap());
-        assert!(program.should_draw(2).unwrap());
-        assert!(!program.should_draw(3).unwrap());
-        assert!(!program.should_draw(4).unwrap());
-        assert!(program.should_draw(5).unwrap());
-        assert!(program.should_draw(6).unwrap());
-        assert!(!program.should_draw(7).unwrap());
-        assert!(!program.should_draw(8).unwrap());
-        assert!(program.should_draw(9).unwrap());
-        assert!(program.should_draw(10).unwrap());
-        assert!(!program.should_draw(11).unwrap());
-        assert!(!program.should_draw(12).unwrap());
-        assert!(program.should_draw(13).unwrap());
-        assert!(program.should_draw(14).unwrap());
-        assert!(!program.should_draw(15).unwrap());
-        assert!(!program.should_draw(16).unwrap());
-        assert!(program.should_draw(17).unwrap());
-        assert!(program.should_draw(18).unwrap());
-        assert!(!program.should_draw(19).unwrap());
-        assert!(!program.should_draw(20).unwrap());
+        assert!(program.should_draw(1, 40).unwrap());
+        assert!(program.should_draw(2, 40).unwrap());
+        assert!(!program.should_draw(3, 40).unwrap());
+        assert!(!program.should_draw(4, 40).unwrap());
+        assert!(program.should_draw(5, 40).unwrap());
+        assert!(program.should_draw(6, 40).unwrap());
+        assert!(!program.should_draw(7, 40).unwrap());
+        assert!(!program.should_draw(8, 40).unwrap());
+        assert!(program.should_draw(9, 40).unwrap());
+        assert!(program.should_draw(10, 40).unwrap());
+        assert!(!program.should_draw(11, 40).unwrap());
+        assert!(!program.should_draw(12, 40).unwrap());
+        assert!(program.should_draw(13, 40).unwrap());
+        assert!(program.should_draw(14, 40).unwrap());
+        assert!(!program.should_draw(15, 40).unwrap());
+        assert!(!program.should_draw(16, 40).unwrap());
+        assert!(program.should_draw(17, 40).unwrap());
+        assert!(program.should_draw(18, 40).unwrap());
+        assert!(!program.should_draw(19, 40).unwrap());
+        assert!(!program.should_draw(20, 40).unwrap());
     }
 
     #[test]
     fn part2() {
-        let program = big_test_program();
-        assert_eq!(program.render(), "\
-##..##..##..##..##..##..##..##..##..##..
-###...###...###...###...###...###...###.
-####....####....####....####....####....
-#####.....#####.....#####.....#####.....
-######......######......######......####
-#######.......#######.......#######.....
-");
+        common::assert_snapshot!("render", big_test_program().render());
     }
 }
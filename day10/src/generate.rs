@@ -0,0 +1,61 @@
+use common::{Generator, Rng};
+
+use crate::Day10;
+
+// Cycles of a whole screen of 40 by 6 pixels
+const SCREEN_CYCLES: usize = 240;
+
+impl Generator for Day10 {
+    const SIZE: usize = 140;
+
+    // `size` is the number of instructions, or more if needed to run for
+    // the 240 cycles of the screen. X stays on the screen, between 0 and 39.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+        let mut x: i64 = 1;
+        let mut instructions = 0;
+        let mut cycles = 0;
+
+        while instructions < size || cycles < SCREEN_CYCLES {
+            let target = rng.range(0..=39) as i64;
+            if target == x || rng.chance(1, 3) {
+                input += "noop\n";
+                cycles += 1;
+            } else {
+                input += &format!("addx {}\n", target - x);
+                x = target;
+                cycles += 2;
+            }
+            instructions += 1;
+        }
+
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn generated_inputs_are_valid() {
+        for seed in 0..20 {
+            let input = common::generate::<Day10>(seed as usize * 20, seed);
+            assert!(input.lines().count() >= seed as usize * 20);
+
+            let program = Day10::parse(&input).unwrap();
+            assert!(Day10::part1(&program, &Default::default()).is_some());
+            let screen = Day10::part2(&program, &Default::default()).unwrap();
+            assert!(screen.lines().count() >= 6);
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(
+            common::generate::<Day10>(50, 1),
+            common::generate::<Day10>(50, 1)
+        );
+    }
+}
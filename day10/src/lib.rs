@@ -0,0 +1,90 @@
+//! Day 10: Cathode-Ray Tube.
+//!
+//! The input is a program for the device's CPU, whose X register
+//! drives both the signal strength and the sprite drawn on the CRT.
+//!
+//! ```
+//! use day10::cpu::Program;
+//!
+//! let program = Program::from_input("noop\naddx 3\naddx -5".as_bytes()).unwrap();
+//! assert_eq!(program.x_values(), [1, 1, 1, 4, 4]);
+//! assert_eq!(program.render(), "#####");
+//! ```
+
+use common::visual::Sampling;
+use common::{Animation, ParseError, Part, Solution};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::BufRead;
+use std::time::Duration;
+
+pub mod cpu;
+mod generate;
+
+use cpu::Program;
+
+/// Knobs of the puzzle, the puzzle's own values by default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Options {
+    /// Cycles whose signal strengths are added up in part 1.
+    pub cycles: Vec<usize>,
+    /// Width and height of the CRT, in pixels.
+    pub screen_size: (u16, u16),
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            cycles: vec![20, 60, 100, 140, 180, 220],
+            screen_size: (40, 6),
+        }
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Program;
+    type Options = Options;
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn read(input: impl BufRead) -> Result<Program, ParseError> {
+        Program::from_input(input)
+    }
+
+    fn part1(program: &Program, options: &Options) -> Option<i32> {
+        program.signal_sum(&options.cycles)
+    }
+
+    fn part2(program: &Program, options: &Options) -> Option<String> {
+        Some(program.render_on(options.screen_size))
+    }
+
+    fn details(program: &Program, options: &Options) -> Option<Value> {
+        let pixels = program.pixels_on(options.screen_size);
+        Some(json!({
+            "crt": {
+                "width": options.screen_size.0,
+                "height": pixels.len(),
+                "pixels": pixels,
+            }
+        }))
+    }
+
+    // The same program runs for both parts
+    fn animation(
+        program: &Program,
+        _part: Part,
+        options: &Options,
+        max_frames: usize,
+    ) -> Option<Animation> {
+        let frames = program.frames_on(options.screen_size, max_frames);
+        let sampling = Sampling::new(program.drawn_cycles(options.screen_size), max_frames);
+        Some(Animation::new(
+            frames,
+            sampling.delay(Duration::from_millis(50)),
+        ))
+    }
+}
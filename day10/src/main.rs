@@ -1,22 +1,12 @@
-#![allow(unused)]
-use std::fs;
+use std::fs::File;
+use std::io::BufReader;
 
-mod cpu;
+use common::ParseError;
+use day10::Day10;
 
-use cpu::Program;
+fn main() -> Result<(), ParseError> {
+    let input = File::open("data/input.txt").expect("Input file should exist at data/input.txt");
+    common::print_answers::<Day10>(BufReader::new(input))?;
 
-fn main() {
-    let s = fs::read_to_string("data/input.txt").unwrap();
-    let program = Program::from_input(&s);
-
-    let sum = program.puzzle_sum().unwrap();
-    println!(
-        "The sum of the signal strengths at
-the 20th, 60th, ..., 220th cycles is: {}",
-        sum
-    );
-
-    let render = program.render();
-    println!("And the rendered image is");
-    println!("{}", render);
+    Ok(())
 }